@@ -0,0 +1,3 @@
+ALTER TABLE tasks ADD COLUMN late_start TEXT;
+ALTER TABLE tasks ADD COLUMN late_finish TEXT;
+ALTER TABLE tasks ADD COLUMN free_slack INTEGER;
@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS baselines
+(
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    name       TEXT NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (name)
+);
+
+CREATE TABLE IF NOT EXISTS baseline_tasks
+(
+    baseline_id INTEGER NOT NULL,
+    task_id     INTEGER NOT NULL,
+    duration    INTEGER NOT NULL,
+    start_date  TEXT    NOT NULL,
+    finish_date TEXT    NOT NULL,
+    total_slack INTEGER NOT NULL,
+    PRIMARY KEY (baseline_id, task_id),
+    FOREIGN KEY (baseline_id) REFERENCES baselines (id)
+);
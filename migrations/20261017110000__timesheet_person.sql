@@ -0,0 +1,6 @@
+ALTER TABLE timesheet ADD COLUMN person TEXT;
+
+-- Work logged so far was done by whoever the task is assigned to
+UPDATE timesheet
+SET person = (SELECT td.assignee FROM task_data td WHERE td.task_id = timesheet.task_id)
+WHERE person IS NULL;
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS task_progress
+(
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id          INTEGER NOT NULL,
+    date             TEXT    NOT NULL,
+    percent_complete INTEGER NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks (id)
+);
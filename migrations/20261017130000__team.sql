@@ -0,0 +1,24 @@
+CREATE TABLE IF NOT EXISTS team
+(
+    id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    name      TEXT    NOT NULL COLLATE NOCASE,
+    role      TEXT,
+    capacity  REAL    NOT NULL DEFAULT 1.0,
+    cost_rate REAL    NOT NULL DEFAULT 0.0,
+    active    INTEGER NOT NULL DEFAULT 1,
+    UNIQUE (name)
+);
+
+-- Everyone tasks were assigned to so far joins the team, their role is not known yet
+INSERT OR IGNORE INTO team (name)
+SELECT DISTINCT lower(assignee)
+FROM task_data
+WHERE assignee IS NOT NULL;
+
+ALTER TABLE task_data ADD COLUMN member_id INTEGER REFERENCES team (id);
+
+UPDATE task_data
+SET member_id = (SELECT team.id FROM team WHERE team.name = task_data.assignee)
+WHERE assignee IS NOT NULL;
+
+ALTER TABLE task_data DROP COLUMN assignee;
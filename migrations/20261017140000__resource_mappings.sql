@@ -0,0 +1,7 @@
+-- Which team member fills a planned MS Project resource, e.g. DEV3
+CREATE TABLE IF NOT EXISTS resource_mappings
+(
+    resource_name TEXT    NOT NULL PRIMARY KEY COLLATE NOCASE,
+    member_id     INTEGER NOT NULL,
+    FOREIGN KEY (member_id) REFERENCES team (id)
+);
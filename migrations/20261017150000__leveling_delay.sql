@@ -0,0 +1,2 @@
+-- Working days a task is held back after its predecessors to resolve over-allocation
+ALTER TABLE tasks ADD COLUMN leveling_delay INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,22 @@
+-- Working weekdays of the project, or of a team member when member_id is set
+CREATE TABLE IF NOT EXISTS calendars
+(
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    member_id     INTEGER,
+    work_weekdays TEXT NOT NULL,
+    UNIQUE (member_id),
+    FOREIGN KEY (member_id) REFERENCES team (id)
+);
+
+INSERT INTO calendars (member_id, work_weekdays)
+VALUES (NULL, 'Mon,Tue,Wed,Thu,Fri');
+
+-- Public holidays of the project, or vacation of a team member when member_id is set
+CREATE TABLE IF NOT EXISTS calendar_days_off
+(
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    member_id   INTEGER,
+    date        TEXT NOT NULL,
+    description TEXT,
+    FOREIGN KEY (member_id) REFERENCES team (id)
+);
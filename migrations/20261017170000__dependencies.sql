@@ -0,0 +1,46 @@
+-- Typed links between tasks, replacing the predecessors as MS Project writes them
+CREATE TABLE IF NOT EXISTS dependencies
+(
+    task_id        INTEGER NOT NULL,
+    predecessor_id INTEGER NOT NULL,
+    -- FS, SS, FF or SF
+    link_type      TEXT    NOT NULL DEFAULT 'FS',
+    -- Working days, negative for lead time
+    lag            INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (task_id, predecessor_id),
+    FOREIGN KEY (task_id) REFERENCES tasks (id),
+    FOREIGN KEY (predecessor_id) REFERENCES tasks (id)
+);
+
+-- Split the stored lists like "3,5SS+2 days" into one row per link, SQLite reads the leading
+-- number of a text cast to an integer
+WITH RECURSIVE links(task_id, link, rest) AS (
+    SELECT id, '', predecessors || ','
+    FROM tasks
+    WHERE predecessors IS NOT NULL
+    UNION ALL
+    SELECT task_id,
+           upper(trim(substr(rest, 1, instr(rest, ',') - 1))),
+           substr(rest, instr(rest, ',') + 1)
+    FROM links
+    WHERE rest != ''
+)
+INSERT OR IGNORE INTO dependencies (task_id, predecessor_id, link_type, lag)
+SELECT task_id,
+       CAST(link AS INTEGER),
+       CASE
+           WHEN link LIKE '%SS%' THEN 'SS'
+           WHEN link LIKE '%FF%' THEN 'FF'
+           WHEN link LIKE '%SF%' THEN 'SF'
+           ELSE 'FS'
+       END,
+       CASE
+           WHEN instr(link, '+') > 0 THEN CAST(trim(substr(link, instr(link, '+') + 1)) AS INTEGER)
+           WHEN instr(link, '-') > 0 THEN -CAST(trim(substr(link, instr(link, '-') + 1)) AS INTEGER)
+           ELSE 0
+       END * CASE WHEN link LIKE '%W%' THEN 5 ELSE 1 END
+FROM links
+WHERE link != ''
+  AND CAST(link AS INTEGER) IN (SELECT id FROM tasks);
+
+ALTER TABLE tasks DROP COLUMN predecessors;
@@ -0,0 +1,8 @@
+-- Total float of every task as it was on a day, recorded whenever the plan is rescheduled
+CREATE TABLE IF NOT EXISTS float_snapshots
+(
+    date        TEXT    NOT NULL,
+    task_id     INTEGER NOT NULL,
+    total_slack INTEGER NOT NULL,
+    PRIMARY KEY (date, task_id)
+);
@@ -0,0 +1,170 @@
+-- Several projects share one database, the one switched to is used when --project is not given
+CREATE TABLE IF NOT EXISTS projects
+(
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    name       TEXT    NOT NULL COLLATE NOCASE,
+    created_at TEXT             DEFAULT CURRENT_TIMESTAMP,
+    is_current INTEGER NOT NULL DEFAULT 0,
+    UNIQUE (name)
+);
+
+-- Everything tracked so far belongs to the first project
+INSERT INTO projects (id, name, is_current)
+VALUES (1, 'default', 1);
+
+-- Task IDs come from MS Project and restart at 1 in every plan, so tasks and everything
+-- referring to them are keyed by project. The tables are rebuilt next to the old ones, the
+-- old ones are dropped children first and the renames carry over the foreign keys.
+CREATE TABLE tasks_new
+(
+    project_id       INTEGER NOT NULL,
+    id               INTEGER NOT NULL,
+    name             TEXT    NOT NULL,
+    duration         INTEGER NOT NULL,
+    start_date       TEXT    NOT NULL,
+    finish_date      TEXT    NOT NULL,
+    total_slack      INTEGER NOT NULL,
+    resource_names   TEXT,
+    pdex_criticality INTEGER,
+    late_start       TEXT,
+    late_finish      TEXT,
+    free_slack       INTEGER,
+    leveling_delay   INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (project_id, id),
+    FOREIGN KEY (project_id) REFERENCES projects (id)
+);
+
+INSERT INTO tasks_new (project_id, id, name, duration, start_date, finish_date, total_slack,
+                       resource_names, pdex_criticality, late_start, late_finish, free_slack,
+                       leveling_delay)
+SELECT 1, id, name, duration, start_date, finish_date, total_slack, resource_names,
+       pdex_criticality, late_start, late_finish, free_slack, leveling_delay
+FROM tasks;
+
+CREATE TABLE task_data_new
+(
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id  INTEGER NOT NULL,
+    task_id     INTEGER,
+    finished_at TEXT DEFAULT NULL,
+    member_id   INTEGER REFERENCES team (id),
+    UNIQUE (project_id, task_id),
+    FOREIGN KEY (project_id, task_id) REFERENCES tasks_new (project_id, id)
+);
+
+INSERT INTO task_data_new (id, project_id, task_id, finished_at, member_id)
+SELECT id, 1, task_id, finished_at, member_id
+FROM task_data;
+
+CREATE TABLE timesheet_new
+(
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id INTEGER NOT NULL,
+    task_id    INTEGER,
+    date       TEXT,
+    duration   INTEGER,
+    person     TEXT,
+    FOREIGN KEY (project_id, task_id) REFERENCES tasks_new (project_id, id)
+);
+
+INSERT INTO timesheet_new (id, project_id, task_id, date, duration, person)
+SELECT id, 1, task_id, date, duration, person
+FROM timesheet;
+
+CREATE TABLE task_progress_new
+(
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id       INTEGER NOT NULL,
+    task_id          INTEGER NOT NULL,
+    date             TEXT    NOT NULL,
+    percent_complete INTEGER NOT NULL,
+    FOREIGN KEY (project_id, task_id) REFERENCES tasks_new (project_id, id)
+);
+
+INSERT INTO task_progress_new (id, project_id, task_id, date, percent_complete)
+SELECT id, 1, task_id, date, percent_complete
+FROM task_progress;
+
+CREATE TABLE dependencies_new
+(
+    project_id     INTEGER NOT NULL,
+    task_id        INTEGER NOT NULL,
+    predecessor_id INTEGER NOT NULL,
+    -- FS, SS, FF or SF
+    link_type      TEXT    NOT NULL DEFAULT 'FS',
+    -- Working days, negative for lead time
+    lag            INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (project_id, task_id, predecessor_id),
+    FOREIGN KEY (project_id, task_id) REFERENCES tasks_new (project_id, id),
+    FOREIGN KEY (project_id, predecessor_id) REFERENCES tasks_new (project_id, id)
+);
+
+INSERT INTO dependencies_new (project_id, task_id, predecessor_id, link_type, lag)
+SELECT 1, task_id, predecessor_id, link_type, lag
+FROM dependencies;
+
+DROP TABLE task_data;
+DROP TABLE timesheet;
+DROP TABLE task_progress;
+DROP TABLE dependencies;
+DROP TABLE tasks;
+
+ALTER TABLE tasks_new RENAME TO tasks;
+ALTER TABLE task_data_new RENAME TO task_data;
+ALTER TABLE timesheet_new RENAME TO timesheet;
+ALTER TABLE task_progress_new RENAME TO task_progress;
+ALTER TABLE dependencies_new RENAME TO dependencies;
+
+-- Baseline names only need to be unique within a project, their tasks follow the baseline
+CREATE TABLE baselines_new
+(
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id INTEGER NOT NULL,
+    name       TEXT    NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (project_id, name),
+    FOREIGN KEY (project_id) REFERENCES projects (id)
+);
+
+INSERT INTO baselines_new (id, project_id, name, created_at)
+SELECT id, 1, name, created_at
+FROM baselines;
+
+CREATE TABLE baseline_tasks_new
+(
+    baseline_id INTEGER NOT NULL,
+    task_id     INTEGER NOT NULL,
+    duration    INTEGER NOT NULL,
+    start_date  TEXT    NOT NULL,
+    finish_date TEXT    NOT NULL,
+    total_slack INTEGER NOT NULL,
+    PRIMARY KEY (baseline_id, task_id),
+    FOREIGN KEY (baseline_id) REFERENCES baselines_new (id)
+);
+
+INSERT INTO baseline_tasks_new (baseline_id, task_id, duration, start_date, finish_date, total_slack)
+SELECT baseline_id, task_id, duration, start_date, finish_date, total_slack
+FROM baseline_tasks;
+
+DROP TABLE baseline_tasks;
+DROP TABLE baselines;
+
+ALTER TABLE baselines_new RENAME TO baselines;
+ALTER TABLE baseline_tasks_new RENAME TO baseline_tasks;
+
+CREATE TABLE float_snapshots_new
+(
+    project_id  INTEGER NOT NULL,
+    date        TEXT    NOT NULL,
+    task_id     INTEGER NOT NULL,
+    total_slack INTEGER NOT NULL,
+    PRIMARY KEY (project_id, date, task_id)
+);
+
+INSERT INTO float_snapshots_new (project_id, date, task_id, total_slack)
+SELECT 1, date, task_id, total_slack
+FROM float_snapshots;
+
+DROP TABLE float_snapshots;
+
+ALTER TABLE float_snapshots_new RENAME TO float_snapshots;
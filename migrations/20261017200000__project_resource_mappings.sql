@@ -0,0 +1,20 @@
+-- Resource names like DEV3 are planned per project, so is who fills them
+CREATE TABLE resource_mappings_new
+(
+    project_id    INTEGER NOT NULL,
+    resource_name TEXT    NOT NULL COLLATE NOCASE,
+    member_id     INTEGER NOT NULL,
+    PRIMARY KEY (project_id, resource_name),
+    FOREIGN KEY (project_id) REFERENCES projects (id),
+    FOREIGN KEY (member_id) REFERENCES team (id)
+);
+
+-- Every existing project keeps the mappings it used so far
+INSERT INTO resource_mappings_new (project_id, resource_name, member_id)
+SELECT p.id, rm.resource_name, rm.member_id
+FROM projects p
+         CROSS JOIN resource_mappings rm;
+
+DROP TABLE resource_mappings;
+
+ALTER TABLE resource_mappings_new RENAME TO resource_mappings;
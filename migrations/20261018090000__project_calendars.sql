@@ -0,0 +1,40 @@
+-- Working weekdays, holidays and vacations differ between projects, e.g. when they are run
+-- from different countries, so calendars are keyed by project like the plans
+CREATE TABLE calendars_new
+(
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id    INTEGER NOT NULL,
+    member_id     INTEGER,
+    work_weekdays TEXT    NOT NULL,
+    UNIQUE (project_id, member_id),
+    FOREIGN KEY (project_id) REFERENCES projects (id),
+    FOREIGN KEY (member_id) REFERENCES team (id)
+);
+
+-- Every existing project keeps the calendars it used so far
+INSERT INTO calendars_new (project_id, member_id, work_weekdays)
+SELECT p.id, c.member_id, c.work_weekdays
+FROM projects p
+         CROSS JOIN calendars c;
+
+CREATE TABLE calendar_days_off_new
+(
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id  INTEGER NOT NULL,
+    member_id   INTEGER,
+    date        TEXT    NOT NULL,
+    description TEXT,
+    FOREIGN KEY (project_id) REFERENCES projects (id),
+    FOREIGN KEY (member_id) REFERENCES team (id)
+);
+
+INSERT INTO calendar_days_off_new (project_id, member_id, date, description)
+SELECT p.id, d.member_id, d.date, d.description
+FROM projects p
+         CROSS JOIN calendar_days_off d;
+
+DROP TABLE calendars;
+DROP TABLE calendar_days_off;
+
+ALTER TABLE calendars_new RENAME TO calendars;
+ALTER TABLE calendar_days_off_new RENAME TO calendar_days_off;
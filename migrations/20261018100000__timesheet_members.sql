@@ -0,0 +1,7 @@
+-- Work logged by team members follows them when they are renamed or leave the team, names
+-- outside the team are only kept in person
+ALTER TABLE timesheet ADD COLUMN member_id INTEGER REFERENCES team (id);
+
+UPDATE timesheet
+SET member_id = (SELECT team.id FROM team WHERE team.name = trim(timesheet.person))
+WHERE person IS NOT NULL;
@@ -0,0 +1,196 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail};
+use chrono::format::{Item, StrftimeItems};
+use serde::Deserialize;
+
+use crate::project::chart::ChartFormat;
+
+const CONFIG_FILE: &str = "project-manager.toml";
+const DEFAULT_DATABASE_FILE: &str = "./db/tasks.db";
+
+// Settings from project-manager.toml, everything left out keeps its default
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Config {
+    // Database file, PROJECT_MANAGER_DB_FILE takes precedence
+    pub(crate) database: Option<String>,
+    pub(crate) slack: SlackThresholds,
+    pub(crate) charts: ChartSettings,
+    pub(crate) dates: DateFormats,
+    // Team members added to the database when missing
+    pub(crate) team: Vec<TeamMember>,
+}
+
+// Largest slack in days of tasks shown red and yellow, tasks with more are green
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct SlackThresholds {
+    pub(crate) red: i64,
+    pub(crate) yellow: i64,
+}
+
+impl Default for SlackThresholds {
+    fn default() -> Self {
+        SlackThresholds {
+            red: 10,
+            yellow: 30,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ChartSettings {
+    // Folder generated chart names are proposed in
+    pub(crate) folder: PathBuf,
+    // Size of rendered images in pixels
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    // Format used when neither --format nor the output file tell, prompts when absent
+    pub(crate) format: Option<ChartFormat>,
+}
+
+impl Default for ChartSettings {
+    fn default() -> Self {
+        ChartSettings {
+            folder: PathBuf::from("charts"),
+            width: 1800,
+            height: 1000,
+            format: None,
+        }
+    }
+}
+
+// strftime formats of dates in tables and messages, and of the shorter dates in prompts
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct DateFormats {
+    pub(crate) display: String,
+    pub(crate) short: String,
+}
+
+impl Default for DateFormats {
+    fn default() -> Self {
+        DateFormats {
+            display: "%a %d.%m.%y".to_string(),
+            short: "%d.%m.%y".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct TeamMember {
+    pub(crate) name: String,
+    pub(crate) role: Option<String>,
+    #[serde(default = "full_time")]
+    pub(crate) capacity: f64,
+    #[serde(default)]
+    pub(crate) cost_rate: f64,
+}
+
+fn full_time() -> f64 {
+    1.0
+}
+
+impl Config {
+    // Read the config from PROJECT_MANAGER_CONFIG, or from project-manager.toml next to the
+    // database or in the current folder. Without a file the defaults are used.
+    pub(crate) fn load() -> anyhow::Result<Config> {
+        let path = match std::env::var("PROJECT_MANAGER_CONFIG") {
+            Ok(path) => Some(PathBuf::from(path)),
+            Err(_) => {
+                // The database in the config itself cannot tell where to look
+                let database_file = std::env::var("PROJECT_MANAGER_DB_FILE")
+                    .unwrap_or(DEFAULT_DATABASE_FILE.to_string());
+                Path::new(&database_file)
+                    .parent()
+                    .map(|folder| folder.join(CONFIG_FILE))
+                    .into_iter()
+                    .chain([PathBuf::from(CONFIG_FILE)])
+                    .find(|path| path.exists())
+            }
+        };
+        let Some(path) = path else {
+            return Ok(Config::default());
+        };
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Could not read config {}: {}", path.display(), e))?;
+        let config: Config = toml::from_str(&content)
+            .map_err(|e| anyhow!("Invalid config {}: {}", path.display(), e))?;
+        config
+            .check()
+            .map_err(|e| anyhow!("Invalid config {}: {}", path.display(), e))?;
+        Ok(config)
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.slack.red < 0 || self.slack.yellow < self.slack.red {
+            bail!("slack thresholds must be 0 <= red <= yellow");
+        }
+        if self.charts.width == 0 || self.charts.height == 0 {
+            bail!("chart width and height must be more than 0");
+        }
+        for format in [&self.dates.display, &self.dates.short] {
+            if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                bail!("{} is not a valid date format", format);
+            }
+        }
+        for member in &self.team {
+            if member.name.trim().is_empty() || member.capacity <= 0.0 {
+                bail!("team members need a name and a capacity above 0");
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn database_file(&self) -> String {
+        std::env::var("PROJECT_MANAGER_DB_FILE")
+            .ok()
+            .or_else(|| self.database.clone())
+            .unwrap_or(DEFAULT_DATABASE_FILE.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> anyhow::Result<Config> {
+        let config: Config = toml::from_str(content)?;
+        config.check()?;
+        Ok(config)
+    }
+
+    #[test]
+    fn example_config_is_valid() {
+        let config = parse(include_str!("../examples/project-manager.toml")).unwrap();
+        assert_eq!(config.database.as_deref(), Some("./db/tasks.db"));
+        assert_eq!(config.team.len(), 2);
+        assert_eq!(config.team[1].capacity, 0.5);
+    }
+
+    #[test]
+    fn left_out_settings_keep_their_defaults() {
+        let config = parse("[slack]\nred = 5\n\n[[team]]\nname = \"Dana\"\n").unwrap();
+        assert_eq!(config.slack.red, 5);
+        assert_eq!(config.slack.yellow, 30);
+        assert_eq!(config.team[0].capacity, 1.0);
+        assert_eq!(config.team[0].cost_rate, 0.0);
+        assert_eq!(config.dates.display, Config::default().dates.display);
+    }
+
+    #[test]
+    fn refuses_invalid_settings() {
+        assert!(parse("colour = \"red\"").is_err());
+        assert!(parse("[slack]\nred = 20\nyellow = 10").is_err());
+        assert!(parse("[slack]\nred = -1").is_err());
+        assert!(parse("[charts]\nwidth = 0").is_err());
+        assert!(parse("[dates]\ndisplay = \"%Q\"").is_err());
+        assert!(parse("[[team]]\nname = \" \"").is_err());
+        assert!(parse("[[team]]\nname = \"Bob\"\ncapacity = 0.0").is_err());
+    }
+}
@@ -3,9 +3,8 @@ extern crate prettytable;
 
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::exit;
 
-use chrono::Datelike;
+use chrono::NaiveDate;
 use clap::{Parser, Subcommand};
 use inquire::{InquireError, Select};
 use promptly::prompt_default;
@@ -13,19 +12,31 @@ use sqlx::migrate::MigrateDatabase;
 use sqlx::sqlite::SqlitePool;
 use sqlx::{Pool, Sqlite};
 
-use project::{earned_value, TaskStatus};
+use config::Config;
+use project::chart::{ChartFormat, ChartOutput};
+use project::earned_value::EvMethod;
+use project::timeline::Zoom;
+use project::{
+    allocation, baseline, calendar, dashboard, earned_value, float, gantt, projects, risk,
+    schedule, staffing, sync, team, timeline, timesheet, validate, ListFormat, TaskStatus,
+};
 
+mod config;
 mod project;
 
 #[derive(Parser)]
 #[command(
     author,
     version,
-    about = "Project management done right in the terminal 👔 \n Database file is read from the ENV-variable PROJECT_MANAGER_DB_FILE or defaults to ./db/tasks.db",
+    about = "Project management done right in the terminal 👔 \n Database file is read from the ENV-variable PROJECT_MANAGER_DB_FILE, the database of the config or defaults to ./db/tasks.db \n Settings are read from PROJECT_MANAGER_CONFIG or project-manager.toml next to the database or in the current folder",
     long_about = "Project management tool to support The Method from IDesign"
 )]
 #[command(propagate_version = true)]
 struct Cli {
+    /// Project to work on, the one switched to when absent
+    #[clap(long, global = true)]
+    project: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -33,28 +44,473 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Complete a task
-    Complete {},
+    Complete {
+        /// Task to complete (repeatable), prompts when absent
+        #[clap(short, long)]
+        task: Vec<i64>,
+
+        /// Completion date (YYYY-MM-DD), defaults to now
+        #[clap(short, long)]
+        date: Option<NaiveDate>,
+    },
+
+    /// Import project from MS Project (CSV export or XML file), merging into an existing database
+    Import {
+        /// MS Project file to import, prompts when absent
+        #[clap(short, long)]
+        file: Option<PathBuf>,
 
-    /// Import project from MS Project
-    Import {},
+        /// Do not ask for confirmation
+        #[clap(short, long)]
+        yes: bool,
+    },
 
     /// List tasks ✅
     List {
+        /// Which tasks to list, prompts when absent
+        #[clap(short, long, value_enum)]
+        status: Option<TaskStatus>,
+
         /// Filter tasks
         #[clap(short, long)]
         number_of_tasks: Option<usize>,
+
+        /// Show finish date variance against a baseline
+        #[clap(short, long)]
+        baseline: Option<String>,
+
+        /// Output format, the table is meant for humans, the others for other tools
+        #[clap(short, long, value_enum, default_value_t = ListFormat::Table)]
+        output: ListFormat,
     },
 
     /// Log work
-    log {},
+    Log {
+        /// Task to log work on, prompts when absent
+        #[clap(short, long)]
+        task: Option<i64>,
+
+        /// Day the work was done (YYYY-MM-DD), defaults to today
+        #[clap(short, long)]
+        date: Option<NaiveDate>,
+
+        /// Number of days worked
+        #[clap(long)]
+        days: Option<i32>,
+
+        /// Who did the work, defaults to the assignee of the task
+        #[clap(short, long)]
+        person: Option<String>,
+    },
+
+    /// Record the percent complete of a task in progress
+    Progress {
+        /// Task to update, prompts when absent
+        #[clap(short, long)]
+        task: Option<i64>,
+
+        /// Percent complete (0-100), prompts when absent
+        #[clap(short, long)]
+        percent: Option<u8>,
+
+        /// Date of the update (YYYY-MM-DD), defaults to today
+        #[clap(short, long)]
+        date: Option<NaiveDate>,
+    },
 
     /// Assign task
-    Assign {},
+    Assign {
+        /// Task to assign (repeatable), prompts when absent
+        #[clap(short, long)]
+        task: Vec<i64>,
+
+        /// Team member (name or id) to assign the tasks to
+        #[clap(long)]
+        to: Option<String>,
+
+        /// Assign even when the member's role does not match the planned resources
+        #[clap(long)]
+        force: bool,
+    },
+
+    /// Recalculate the schedule from the task dependencies (critical path)
+    Schedule {
+        /// Do not schedule remaining work before this date (YYYY-MM-DD)
+        #[clap(short, long)]
+        status_date: Option<NaiveDate>,
+    },
 
     /// Generate earned value chart
     EV {
         #[clap(short, long)]
         chart_title: Option<String>,
+
+        /// Draw the planned progress of a baseline next to the current plan (repeatable)
+        #[clap(short, long)]
+        baseline: Vec<String>,
+
+        /// Chart format, taken from the output file extension or prompted when absent
+        #[clap(short, long, value_enum)]
+        format: Option<ChartFormat>,
+
+        /// Output file, prompts when absent
+        #[clap(short, long)]
+        out: Option<PathBuf>,
+
+        /// Open the chart after saving it
+        #[clap(long)]
+        open: bool,
+
+        /// How tasks in progress earn value
+        #[clap(short, long, value_enum, global = true, default_value_t = EvMethod::ZeroHundred)]
+        method: EvMethod,
+
+        #[command(subcommand)]
+        mode: Option<EvMode>,
+    },
+
+    /// Find days on which people are assigned more tasks than they can work on
+    Allocation {
+        /// Delay non-critical tasks within their slack to resolve over-allocation
+        #[clap(short, long)]
+        level: bool,
+
+        /// Apply the leveled schedule without asking
+        #[clap(short, long)]
+        yes: bool,
+    },
+
+    /// Generate staffing distribution chart, planned headcount per role and actual staffing
+    Staffing {
+        #[clap(short, long)]
+        chart_title: Option<String>,
+
+        /// Chart format, taken from the output file extension or prompted when absent
+        #[clap(short, long, value_enum)]
+        format: Option<ChartFormat>,
+
+        /// Output file, prompts when absent
+        #[clap(short, long)]
+        out: Option<PathBuf>,
+
+        /// Open the chart after saving it
+        #[clap(long)]
+        open: bool,
+    },
+
+    /// Gantt chart of the plan with dependencies, the critical path and today
+    Gantt {
+        #[clap(short, long)]
+        chart_title: Option<String>,
+
+        /// Chart format, taken from the output file extension or prompted when absent
+        #[clap(short, long, value_enum)]
+        format: Option<ChartFormat>,
+
+        /// Output file, prompts when absent
+        #[clap(short, long)]
+        out: Option<PathBuf>,
+
+        /// Open the chart after saving it
+        #[clap(long)]
+        open: bool,
+    },
+
+    /// Gantt view in the terminal, bars are colored by slack like in `list`
+    Timeline {
+        /// Which tasks to show
+        #[clap(short, long, value_enum, default_value_t = TaskStatus::All)]
+        status: TaskStatus,
+
+        /// Time covered by one character
+        #[clap(short, long, value_enum, default_value_t = Zoom::Week)]
+        zoom: Zoom,
+
+        /// Only show the next weeks, starting with the current one
+        #[clap(short, long)]
+        weeks: Option<u32>,
+    },
+
+    /// Full-screen task table to assign, complete and log work on tasks
+    Dashboard {},
+
+    /// Check the dependencies of a MS Project file or of the imported plan
+    Validate {
+        /// MS Project file (CSV export or XML file) to check, the database when absent
+        #[clap(short, long)]
+        file: Option<PathBuf>,
+    },
+
+    /// Criticality and activity risk of the plan and of every baseline
+    Risk {},
+
+    /// Track how much float the tasks have burnt since a baseline
+    Float {
+        #[command(subcommand)]
+        command: FloatCommands,
+    },
+
+    /// Capture and list plan baselines
+    Baseline {
+        #[command(subcommand)]
+        command: BaselineCommands,
+    },
+
+    /// Manage the team members tasks are assigned to
+    Team {
+        #[command(subcommand)]
+        command: TeamCommands,
+    },
+
+    /// List, amend and delete logged work
+    Timesheet {
+        #[command(subcommand)]
+        command: TimesheetCommands,
+    },
+
+    /// Working weekdays, public holidays and vacations
+    Calendar {
+        #[command(subcommand)]
+        command: CalendarCommands,
+    },
+
+    /// Projects tracked in the database, every other command works on one of them
+    Project {
+        #[command(subcommand)]
+        command: ProjectCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProjectCommands {
+    /// Create a project and switch to it
+    New {
+        /// Name of the project
+        name: String,
+    },
+
+    /// List the projects, the one worked on is marked
+    List {},
+
+    /// Work on another project from now on
+    Switch {
+        /// Name of the project
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CalendarCommands {
+    /// Show the working weekdays and upcoming days off
+    Show {
+        /// Calendar of this team member (name or id) instead of the project
+        #[clap(short, long)]
+        member: Option<String>,
+    },
+
+    /// Set the working weekdays, e.g. Mon,Tue,Wed,Thu
+    Weekdays {
+        /// Comma separated weekdays
+        days: String,
+
+        /// Set them for this team member (name or id) instead of the project
+        #[clap(short, long)]
+        member: Option<String>,
+    },
+
+    /// Add or remove a public holiday
+    Holiday {
+        /// Day of the holiday (YYYY-MM-DD)
+        date: NaiveDate,
+
+        /// Name of the holiday
+        #[clap(short, long)]
+        name: Option<String>,
+
+        /// Make it a working day again
+        #[clap(long)]
+        remove: bool,
+    },
+
+    /// Import public holidays from an iCal (.ics) file
+    Import {
+        /// The iCal file with the holidays
+        file: PathBuf,
+    },
+
+    /// Add or remove a vacation of a team member
+    Vacation {
+        /// Name or id of the team member
+        member: String,
+
+        /// First day off (YYYY-MM-DD)
+        from: NaiveDate,
+
+        /// Last day off (YYYY-MM-DD), defaults to a single day
+        to: Option<NaiveDate>,
+
+        /// Cancel the vacation
+        #[clap(long)]
+        remove: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TeamCommands {
+    /// Add a team member
+    Add {
+        /// Name of the team member
+        name: String,
+
+        /// Role as used for the MS Project resources, e.g. ARC, DEV, UX, TEST
+        #[clap(short, long)]
+        role: Option<String>,
+
+        /// Share of a full time position available to the project
+        #[clap(short, long, default_value_t = 1.0)]
+        capacity: f64,
+
+        /// Cost per day
+        #[clap(long, default_value_t = 0.0)]
+        cost_rate: f64,
+    },
+
+    /// Remove a team member
+    Remove {
+        /// Name or id of the team member
+        member: String,
+
+        /// Do not ask for confirmation
+        #[clap(short, long)]
+        yes: bool,
+    },
+
+    /// List team members
+    List {},
+
+    /// Map a planned MS Project resource (e.g. DEV3) to a member and assign all its tasks
+    Map {
+        /// Resource name as used in the MS Project plan
+        resource: String,
+
+        /// Name or id of the team member
+        member: String,
+
+        /// Do not ask for confirmation
+        #[clap(short, long)]
+        yes: bool,
+
+        /// Map even when the member's role does not match the resource
+        #[clap(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TimesheetCommands {
+    /// List logged work
+    List {
+        /// Only work logged on this task
+        #[clap(short, long)]
+        task: Option<i64>,
+
+        /// Only work logged by this person
+        #[clap(short, long)]
+        person: Option<String>,
+
+        /// Only work logged on or after this date (YYYY-MM-DD)
+        #[clap(short, long)]
+        from: Option<NaiveDate>,
+
+        /// Only work logged on or before this date (YYYY-MM-DD)
+        #[clap(long)]
+        to: Option<NaiveDate>,
+    },
+
+    /// Amend a timesheet entry, prompts for the new values when no flags are given
+    Edit {
+        /// Id of the timesheet entry
+        id: i64,
+
+        /// Move the work to another task
+        #[clap(short, long)]
+        task: Option<i64>,
+
+        /// Day the work was done (YYYY-MM-DD)
+        #[clap(short, long)]
+        date: Option<NaiveDate>,
+
+        /// Number of days worked
+        #[clap(long)]
+        days: Option<i32>,
+
+        /// Who did the work
+        #[clap(short, long)]
+        person: Option<String>,
+
+        /// Do not ask for confirmation
+        #[clap(short, long)]
+        yes: bool,
+    },
+
+    /// Days logged per person and week
+    Report {
+        /// Only work logged on or after this date (YYYY-MM-DD)
+        #[clap(short, long)]
+        from: Option<NaiveDate>,
+
+        /// Only work logged on or before this date (YYYY-MM-DD)
+        #[clap(long)]
+        to: Option<NaiveDate>,
+    },
+
+    /// Delete a timesheet entry
+    Delete {
+        /// Id of the timesheet entry
+        id: i64,
+
+        /// Do not ask for confirmation
+        #[clap(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum FloatCommands {
+    /// Record the float of every task today, rescheduling records it as well
+    Snapshot {},
+
+    /// Float burnt by each chain of tasks and the tasks that have become near-critical
+    Report {
+        /// Baseline to compare with, the latest one when absent
+        #[clap(short, long)]
+        baseline: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BaselineCommands {
+    /// Snapshot the dates, durations and slack of every task
+    Save {
+        /// Name of the baseline
+        name: String,
+    },
+
+    /// List captured baselines
+    List {},
+}
+
+#[derive(Subcommand)]
+enum EvMode {
+    /// Print earned value metrics (SPI, CPI, EAC, ...) instead of drawing the chart
+    Report {
+        /// Status date (YYYY-MM-DD), defaults to the end of the current week
+        #[clap(short, long)]
+        status_date: Option<NaiveDate>,
+
+        /// Print the metrics as JSON
+        #[clap(short, long)]
+        json: bool,
     },
 }
 
@@ -62,111 +518,445 @@ enum Commands {
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    let database_file =
-        std::env::var("PROJECT_MANAGER_DB_FILE").unwrap_or("./db/tasks.db".to_string());
+    let config = Config::load()?;
+    let database_file = config.database_file();
 
     match &cli.command {
-        Commands::Import {} => {
+        Commands::Import { file, yes } => {
             // Migrate database
-            create_database_check(&database_file).await?;
-            let pool = connect_to_db_pool(&database_file).await;
-            sqlx::migrate!().run(&pool).await?;
-            // Prompt Y/n with a default value when input is empty
-            let confirm_continue = prompt_default("💩 Continue importing from MS Project?", false);
-            if confirm_continue.is_err() || confirm_continue.unwrap() != true {
-                println!("👋 Bye!");
-                return Ok(());
+            let created = create_database_check(&database_file).await?;
+            let pool = connect_to_db_pool(&database_file).await?;
+            team::add_configured(&pool, &config.team).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            // A new project is imported as a whole, an existing one gets the changes merged
+            let merge = !created && !projects::is_empty(&pool, project_id).await?;
+            if merge {
+                println!(
+                    "👋 Project exists, changes will be merged into it (using db: {}).",
+                    database_file
+                );
+            }
+            if !yes {
+                // Prompt Y/n with a default value when input is empty
+                let confirm_continue =
+                    prompt_default("💩 Continue importing from MS Project?", false);
+                if confirm_continue.is_err() || !confirm_continue.unwrap() {
+                    println!("👋 Bye!");
+                    return Ok(());
+                }
+            }
+
+            let project_file: PathBuf = match file {
+                Some(file) => file.clone(),
+                None => prompt_default(
+                    "Enter path to MS Project file (.csv or .xml)",
+                    PathBuf::from("tasks.csv"),
+                )?,
+            };
+
+            if merge {
+                sync::merge_plan(
+                    &pool,
+                    project_id,
+                    &config,
+                    project_file.to_string_lossy().into_owned(),
+                    *yes,
+                )
+                .await?;
+            } else {
+                project::import(
+                    &pool,
+                    project_id,
+                    project_file.to_string_lossy().into_owned(),
+                    database_file,
+                )
+                .await?;
             }
+        }
 
-            let project_file: PathBuf =
-                prompt_default("Enter path to MS Project file", PathBuf::from("tasks.csv"))?;
+        Commands::List {
+            status,
+            number_of_tasks,
+            baseline,
+            output,
+        } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
 
-            // get command
-            project::import(
+            let choice = match status {
+                Some(status) => *status,
+                None => prompt_task_status(),
+            };
+            project::list(
                 &pool,
-                project_file.to_string_lossy().into_owned(),
-                database_file,
+                project_id,
+                &config,
+                choice,
+                number_of_tasks,
+                baseline,
+                *output,
+            )
+            .await
+            .expect("Could not list tasks");
+        }
+
+        Commands::Log {
+            task,
+            date,
+            days,
+            person,
+        } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            team::add_configured(&pool, &config.team).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            project::log_work(
+                &pool,
+                project_id,
+                &config,
+                *task,
+                *date,
+                *days,
+                person.clone(),
             )
-            .await;
+            .await
+            .map_err(|e| anyhow::anyhow!("Could not log work: {}", e))?;
+        }
+        Commands::EV {
+            mode: Some(EvMode::Report { status_date, json }),
+            method,
+            ..
+        } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            earned_value::report(&pool, project_id, &config, *status_date, *method, *json).await?;
         }
 
-        Commands::List { number_of_tasks } => {
-            let pool = connect_to_db_pool(&database_file).await;
+        Commands::EV {
+            chart_title,
+            baseline,
+            format,
+            out,
+            open,
+            method,
+            mode: None,
+        } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
 
-            let options: Vec<&str> = vec!["Assigned", "Unassigned", "All", "Completed"];
-            let ans: Result<&str, InquireError> = Select::new("What tasks?", options).prompt();
+            let title = chart_title
+                .clone()
+                .unwrap_or("Earned value chart ✨".to_string());
 
-            let choice = match ans {
-                Ok(choice) => match choice {
-                    "Assigned" => TaskStatus::Assigned,
-                    "Unassigned" => TaskStatus::Unassigned,
-                    "All" => TaskStatus::All,
-                    "Completed" => TaskStatus::Completed,
-                    _ => TaskStatus::All,
-                },
-                Err(_) => TaskStatus::All,
+            let output = ChartOutput {
+                format: *format,
+                out_file: out.clone(),
+                open: *open,
+                settings: config.charts.clone(),
             };
-            project::list(&pool, choice, number_of_tasks)
-                .await
-                .expect("Could not list tasks");
+            earned_value::generate_chart(
+                &pool,
+                project_id,
+                &config,
+                title.as_str(),
+                baseline,
+                *method,
+                &output,
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Could not generate chart 💥: {}", e))?;
         }
 
-        Commands::log { .. } => {
-            let pool = connect_to_db_pool(&database_file).await;
-            project::log_work(&pool).await.expect("Could not log work");
+        Commands::Allocation { level, yes } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            team::add_configured(&pool, &config.team).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            allocation::check(&pool, project_id, &config, *level, *yes).await?;
         }
 
-        Commands::EV { chart_title } => {
-            let pool = connect_to_db_pool(&database_file).await;
+        Commands::Staffing {
+            chart_title,
+            format,
+            out,
+            open,
+        } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            team::add_configured(&pool, &config.team).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
 
             let title = chart_title
                 .clone()
-                .unwrap_or("Earned value chart ✨".to_string());
+                .unwrap_or("Staffing distribution 👥".to_string());
+            let output = ChartOutput {
+                format: *format,
+                out_file: out.clone(),
+                open: *open,
+                settings: config.charts.clone(),
+            };
+            staffing::generate_chart(&pool, project_id, title.as_str(), &output)
+                .await
+                .map_err(|e| anyhow::anyhow!("Could not generate chart 💥: {}", e))?;
+        }
 
-            earned_value::generate_chart(&pool, title.as_str())
+        Commands::Gantt {
+            chart_title,
+            format,
+            out,
+            open,
+        } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+
+            let title = chart_title.clone().unwrap_or("Gantt chart 📊".to_string());
+            let output = ChartOutput {
+                format: *format,
+                out_file: out.clone(),
+                open: *open,
+                settings: config.charts.clone(),
+            };
+            gantt::generate_chart(&pool, project_id, &config, title.as_str(), &output)
                 .await
-                .expect("Could not generate chart 💥");
+                .map_err(|e| anyhow::anyhow!("Could not generate chart 💥: {}", e))?;
         }
 
-        Commands::Complete {} => {
-            let pool = connect_to_db_pool(&database_file).await;
-            project::complete_tasks(&pool).await?;
+        Commands::Timeline {
+            status,
+            zoom,
+            weeks,
+        } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            timeline::show(&pool, project_id, &config, *status, *zoom, *weeks).await?;
         }
-        Commands::Assign { .. } => {
-            let pool = connect_to_db_pool(&database_file).await;
-            project::assign_tasks(pool).await?;
+
+        Commands::Dashboard {} => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            team::add_configured(&pool, &config.team).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            dashboard::run(&pool, project_id, &config).await?;
+        }
+
+        Commands::Validate { file } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            let file = file
+                .as_ref()
+                .map(|file| file.to_string_lossy().into_owned());
+            validate::validate(&pool, project_id, &file).await?;
+        }
+
+        Commands::Risk {} => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            risk::report(&pool, project_id, &config).await?;
+        }
+
+        Commands::Float { command } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            match command {
+                FloatCommands::Snapshot {} => {
+                    float::save_snapshot(&pool, project_id, &config).await?
+                }
+                FloatCommands::Report { baseline } => {
+                    float::report(&pool, project_id, &config, baseline).await?
+                }
+            }
+        }
+
+        Commands::Baseline { command } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            match command {
+                BaselineCommands::Save { name } => baseline::save(&pool, project_id, name).await?,
+                BaselineCommands::List {} => baseline::list(&pool, project_id).await?,
+            }
+        }
+
+        Commands::Progress {
+            task,
+            percent,
+            date,
+        } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            project::update_progress(&pool, project_id, &config, *task, *percent, *date).await?;
+        }
+
+        Commands::Team { command } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            team::add_configured(&pool, &config.team).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            match command {
+                TeamCommands::Add {
+                    name,
+                    role,
+                    capacity,
+                    cost_rate,
+                } => team::add(&pool, name, role, *capacity, *cost_rate).await?,
+                TeamCommands::Remove { member, yes } => team::remove(&pool, member, *yes).await?,
+                TeamCommands::List {} => team::list(&pool, project_id).await?,
+                TeamCommands::Map {
+                    resource,
+                    member,
+                    yes,
+                    force,
+                } => {
+                    project::map_resource(&pool, project_id, resource, member, *yes, *force).await?
+                }
+            }
+        }
+
+        Commands::Timesheet { command } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            match command {
+                TimesheetCommands::List {
+                    task,
+                    person,
+                    from,
+                    to,
+                } => timesheet::list(&pool, project_id, &config, *task, person, *from, *to).await?,
+                TimesheetCommands::Edit {
+                    id,
+                    task,
+                    date,
+                    days,
+                    person,
+                    yes,
+                } => {
+                    timesheet::edit(
+                        &pool,
+                        project_id,
+                        &config,
+                        *id,
+                        timesheet::EntryChanges {
+                            task_id: *task,
+                            date: *date,
+                            days: *days,
+                            person: person.clone(),
+                        },
+                        *yes,
+                    )
+                    .await?
+                }
+                TimesheetCommands::Report { from, to } => {
+                    timesheet::report(&pool, project_id, &config, *from, *to).await?
+                }
+                TimesheetCommands::Delete { id, yes } => {
+                    timesheet::delete(&pool, project_id, &config, *id, *yes).await?
+                }
+            }
+        }
+
+        Commands::Calendar { command } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            team::add_configured(&pool, &config.team).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            match command {
+                CalendarCommands::Show { member } => {
+                    calendar::show(&pool, project_id, &config, member).await?
+                }
+                CalendarCommands::Weekdays { days, member } => {
+                    calendar::set_weekdays(&pool, project_id, &config, days, member).await?
+                }
+                CalendarCommands::Holiday { date, name, remove } => {
+                    calendar::holiday(&pool, project_id, &config, *date, name, *remove).await?
+                }
+                CalendarCommands::Import { file } => {
+                    calendar::import_holidays(&pool, project_id, &config, file).await?
+                }
+                CalendarCommands::Vacation {
+                    member,
+                    from,
+                    to,
+                    remove,
+                } => {
+                    calendar::vacation(&pool, project_id, &config, member, *from, *to, *remove)
+                        .await?
+                }
+            }
+        }
+
+        Commands::Project { command } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            match command {
+                ProjectCommands::New { name } => projects::new(&pool, name).await?,
+                ProjectCommands::List {} => {
+                    let current = projects::select(&pool, &cli.project).await?;
+                    projects::list(&pool, current.id).await?
+                }
+                ProjectCommands::Switch { name } => projects::switch(&pool, name).await?,
+            }
+        }
+
+        Commands::Schedule { status_date } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            schedule::schedule(&pool, project_id, &config, *status_date).await?;
+        }
+
+        Commands::Complete { task, date } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            project::complete_tasks(&pool, project_id, &config, task, *date).await?;
+        }
+        Commands::Assign { task, to, force } => {
+            let pool = connect_to_db_pool(&database_file).await?;
+            team::add_configured(&pool, &config.team).await?;
+            let project_id = projects::select(&pool, &cli.project).await?.id;
+            project::assign_tasks(pool, project_id, &config, task, to.clone(), *force).await?;
         }
     }
     Ok(())
 }
 
-async fn connect_to_db_pool(database_file: &String) -> Pool<Sqlite> {
-    let pool = SqlitePool::connect(&*db_url(database_file))
+fn prompt_task_status() -> TaskStatus {
+    let options: Vec<&str> = vec!["Assigned", "Unassigned", "Pending", "All", "Completed"];
+    let ans: Result<&str, InquireError> = Select::new("What tasks?", options).prompt();
+
+    match ans {
+        Ok(choice) => match choice {
+            "Assigned" => TaskStatus::Assigned,
+            "Unassigned" => TaskStatus::Unassigned,
+            "Pending" => TaskStatus::Pending,
+            "All" => TaskStatus::All,
+            "Completed" => TaskStatus::Completed,
+            _ => TaskStatus::All,
+        },
+        Err(_) => TaskStatus::All,
+    }
+}
+
+async fn connect_to_db_pool(database_file: &String) -> anyhow::Result<Pool<Sqlite>> {
+    let pool = SqlitePool::connect(&db_url(database_file))
         .await
-        .expect(&*format!(
-            "Could not connect to database {}, check if correct folder!",
-            database_file
-        ));
-    pool
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "Could not connect to database {}, check if correct folder! {}",
+                database_file,
+                e
+            )
+        })?;
+    sqlx::migrate!()
+        .run(&pool)
+        .await
+        .map_err(|e| anyhow::anyhow!("Could not migrate database: {}", e))?;
+    Ok(pool)
 }
 
-async fn create_database_check(database_file: &String) -> anyhow::Result<()> {
-    if !Sqlite::database_exists(&*db_url(database_file)).await? {
-        // Create the parent directory if it doesn't exist
-        if let Some(parent_dir) = Path::new(&database_file).parent() {
-            fs::create_dir_all(parent_dir)?;
-        }
-        Sqlite::create_database(&*db_url(database_file)).await?;
-    } else {
-        println!(
-            "👋 Database exists, remove and re-run init (using db: {}).",
-            database_file
-        );
-        exit(1);
+// Creates the database file, returns false when it already exists
+async fn create_database_check(database_file: &String) -> anyhow::Result<bool> {
+    if Sqlite::database_exists(&db_url(database_file)).await? {
+        return Ok(false);
     }
-    Ok(())
+    // Create the parent directory if it doesn't exist
+    if let Some(parent_dir) = Path::new(&database_file).parent() {
+        fs::create_dir_all(parent_dir)?;
+    }
+    Sqlite::create_database(&db_url(database_file)).await?;
+    Ok(true)
 }
 
 fn db_url(database_file: &String) -> String {
-    return format!("sqlite:{}", database_file);
+    format!("sqlite:{}", database_file)
 }
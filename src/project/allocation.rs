@@ -0,0 +1,547 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{Duration, NaiveDate};
+use colored::Colorize;
+use prettytable::{row, Table};
+use promptly::prompt_default;
+use sqlx::{SqliteConnection, SqlitePool};
+use titlecase::titlecase;
+
+use crate::config::{Config, DateFormats};
+use crate::project::calendar::{self, Calendar};
+use crate::project::dfmt;
+use crate::project::schedule::{self, Activity, ScheduledTask};
+
+// Leveling holds tasks back one working day at a time, this bounds the number of steps
+const MAX_LEVELING_STEPS: usize = 2000;
+
+// An open task and the team member it is assigned to
+struct Assignment {
+    task_id: i64,
+    member: String,
+    capacity: f64,
+    calendar: Calendar,
+}
+
+// A day on which someone is planned on more tasks than they can work on
+struct Overload {
+    member: String,
+    date: NaiveDate,
+    tasks: Vec<i64>,
+}
+
+// Report the days people are over-allocated and optionally level the schedule
+pub(crate) async fn check(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    level: bool,
+    yes: bool,
+) -> anyhow::Result<()> {
+    let assignments = load_assignments(pool, project_id).await?;
+    let mut activities = schedule::load_activities(pool, project_id).await?;
+    let project_calendar = calendar::project_calendar(pool, project_id).await?;
+    let scheduled = schedule::compute(&activities, None, &project_calendar)?;
+
+    let overloads = find_overloads(&assignments, &scheduled);
+    let delayed = activities
+        .iter()
+        .any(|activity| activity.leveling_delay != 0);
+    // Leveling again may take back delays that are no longer needed
+    if overloads.is_empty() && !(level && delayed) {
+        println!("✅ Nobody is over-allocated");
+        return Ok(());
+    }
+    if !overloads.is_empty() {
+        print_overloads(&overloads, &config.dates, &project_calendar);
+    }
+    if !level {
+        println!("💡 Run with --level to delay tasks within their slack");
+        return Ok(());
+    }
+
+    let stored: Vec<(NaiveDate, i64)> = activities
+        .iter()
+        .map(|activity| (activity.start_date, activity.leveling_delay))
+        .collect();
+    if !level_activities(&mut activities, &assignments, &project_calendar)? {
+        println!(
+            "⚠️ Leveling stopped after {} steps without resolving every conflict",
+            MAX_LEVELING_STEPS
+        );
+    }
+
+    let leveled = schedule::compute(&activities, None, &project_calendar)?;
+    let mut table = Table::new();
+    table.add_row(row![
+        "#".bold(),
+        "Task".bold(),
+        "Start Date".bold(),
+        "Finish Date".bold(),
+        "Slack".bold(),
+    ]);
+    let mut moved = false;
+    for ((before, after), activity) in scheduled.iter().zip(&leveled).zip(&activities) {
+        if before.early_start != after.early_start || before.early_finish != after.early_finish {
+            table.add_row(row![
+                activity.id.to_string(),
+                activity.name,
+                format!(
+                    "{} → {}",
+                    dfmt(before.early_start, &config.dates),
+                    dfmt(after.early_start, &config.dates)
+                ),
+                format!(
+                    "{} → {}",
+                    dfmt(before.early_finish, &config.dates),
+                    dfmt(after.early_finish, &config.dates)
+                ),
+                format!("{} → {}", before.total_slack, after.total_slack),
+            ]);
+            moved = true;
+        }
+    }
+
+    // Delays dropped without moving the task are stored as well
+    let changed: Vec<&Activity> = activities
+        .iter()
+        .zip(&stored)
+        .filter(|(activity, (start_date, leveling_delay))| {
+            activity.start_date != *start_date || activity.leveling_delay != *leveling_delay
+        })
+        .map(|(activity, _)| activity)
+        .collect();
+    let remaining = find_overloads(&assignments, &leveled);
+    if changed.is_empty() {
+        if remaining.is_empty() {
+            println!("✅ Nobody is over-allocated");
+        } else {
+            println!("⚠️ No task can be delayed within its slack, reassign tasks instead");
+        }
+        return Ok(());
+    }
+    if moved {
+        table.printstd();
+    } else {
+        println!("✨ The delays of earlier leveling runs are no longer needed");
+    }
+    if !remaining.is_empty() {
+        println!(
+            "⚠️ {} over-allocated days remain, they involve critical or started tasks",
+            remaining.len()
+        );
+    }
+
+    if !yes {
+        let confirm = prompt_default("💾 Apply the leveled schedule?", false);
+        if confirm.is_err() || !confirm.unwrap() {
+            println!("👋 Schedule unchanged, bye!");
+            return Ok(());
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+    for activity in changed {
+        let start_date = activity.start_date.format("%Y-%m-%d").to_string();
+        sqlx::query!(
+            r#"
+            UPDATE tasks
+            SET start_date = ?1, leveling_delay = ?2
+            WHERE project_id = ?4 AND id = ?3
+            "#,
+            start_date,
+            activity.leveling_delay,
+            activity.id,
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    schedule::update(pool, project_id, config).await?;
+    Ok(())
+}
+
+// Hold back the task with the most slack on the first day that can be resolved, by the
+// fewest working days that take it out of every conflict. Delays of earlier runs are dropped
+// first, so they never pile up. Tells whether leveling finished within MAX_LEVELING_STEPS.
+fn level_activities(
+    activities: &mut [Activity],
+    assignments: &[Assignment],
+    calendar: &Calendar,
+) -> anyhow::Result<bool> {
+    for activity in activities.iter_mut() {
+        activity.leveling_delay = 0;
+    }
+    let mut stuck: HashSet<i64> = HashSet::new();
+    for _ in 0..MAX_LEVELING_STEPS {
+        let scheduled = schedule::compute(activities, None, calendar)?;
+        let by_id: HashMap<i64, &ScheduledTask> =
+            scheduled.iter().map(|task| (task.id, task)).collect();
+
+        let movable = find_overloads(assignments, &scheduled)
+            .into_iter()
+            .find_map(|overload| {
+                overload
+                    .tasks
+                    .iter()
+                    .filter(|id| !stuck.contains(*id) && by_id[*id].total_slack > 0)
+                    .filter(|id| {
+                        activities
+                            .iter()
+                            .any(|activity| activity.id == **id && activity.logged == 0)
+                    })
+                    .max_by_key(|id| (by_id[*id].total_slack, by_id[*id].early_start))
+                    .map(|id| (*id, by_id[id].total_slack))
+            });
+        let Some((id, slack)) = movable else {
+            return Ok(true);
+        };
+
+        let index = activities
+            .iter()
+            .position(|activity| activity.id == id)
+            .expect("Unknown task");
+        let mut resolved = false;
+        for delay in 1..=slack {
+            activities[index].delay(1, calendar);
+            let scheduled = schedule::compute(activities, None, calendar)?;
+            if !find_overloads(assignments, &scheduled)
+                .iter()
+                .any(|overload| overload.tasks.contains(&id))
+            {
+                resolved = true;
+                break;
+            }
+            if delay == slack {
+                activities[index].delay(-slack, calendar);
+            }
+        }
+        if !resolved {
+            stuck.insert(id);
+        }
+    }
+
+    Ok(false)
+}
+
+// Delays are worked out for the network and calendar of the plan, syncing it drops them until
+// the next leveling run
+pub(crate) async fn reset_leveling(
+    conn: &mut SqliteConnection,
+    project_id: i64,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE tasks SET leveling_delay = 0 WHERE project_id = ?1"#,
+        project_id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+// Warn right away when an assignment puts someone on concurrent tasks
+pub(crate) async fn warn_overallocated(
+    pool: &SqlitePool,
+    project_id: i64,
+    member: &str,
+) -> anyhow::Result<()> {
+    let assignments: Vec<Assignment> = load_assignments(pool, project_id)
+        .await?
+        .into_iter()
+        .filter(|assignment| assignment.member.eq_ignore_ascii_case(member))
+        .collect();
+    let activities = schedule::load_activities(pool, project_id).await?;
+    let project_calendar = calendar::project_calendar(pool, project_id).await?;
+    let scheduled = schedule::compute(&activities, None, &project_calendar)?;
+
+    let overloads = find_overloads(&assignments, &scheduled);
+    if !overloads.is_empty() {
+        println!(
+            "⚠️ {} is over-allocated on {} days, see `allocation`",
+            member,
+            overloads.len()
+        );
+    }
+    Ok(())
+}
+
+async fn load_assignments(pool: &SqlitePool, project_id: i64) -> anyhow::Result<Vec<Assignment>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT td.task_id  as "task_id!",
+               tm.id       as "member_id!",
+               tm.name     as "member!",
+               tm.capacity as "capacity!"
+        FROM task_data td
+                 JOIN team tm ON tm.id = td.member_id
+        WHERE td.project_id = ?1
+          AND td.finished_at IS NULL
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let calendars = calendar::member_calendars(pool, project_id).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| Assignment {
+            task_id: row.task_id,
+            member: row.member,
+            capacity: row.capacity,
+            calendar: calendars[&row.member_id].clone(),
+        })
+        .collect())
+}
+
+// People work on one task at a time, a capacity of 2.0 or more allows concurrent tasks
+fn find_overloads(assignments: &[Assignment], scheduled: &[ScheduledTask]) -> Vec<Overload> {
+    let by_id: HashMap<i64, &ScheduledTask> =
+        scheduled.iter().map(|task| (task.id, task)).collect();
+
+    let mut load: BTreeMap<(NaiveDate, &str), Vec<i64>> = BTreeMap::new();
+    for assignment in assignments {
+        let Some(task) = by_id.get(&assignment.task_id) else {
+            continue;
+        };
+        let mut date = task.early_start;
+        while date <= task.early_finish {
+            if assignment.calendar.is_workday(date) {
+                load.entry((date, assignment.member.as_str()))
+                    .or_default()
+                    .push(assignment.task_id);
+            }
+            date += Duration::days(1);
+        }
+    }
+
+    let capacity: HashMap<&str, f64> = assignments
+        .iter()
+        .map(|assignment| (assignment.member.as_str(), assignment.capacity))
+        .collect();
+    load.into_iter()
+        .filter(|((_, member), tasks)| tasks.len() as f64 > capacity[member].max(1.0).floor())
+        .map(|((date, member), tasks)| Overload {
+            member: member.to_string(),
+            date,
+            tasks,
+        })
+        .collect()
+}
+
+// One row per person and period of consecutive over-allocated days on the same tasks
+fn print_overloads(overloads: &[Overload], dates: &DateFormats, calendar: &Calendar) {
+    let mut periods: Vec<(&str, NaiveDate, NaiveDate, &Vec<i64>)> = vec![];
+    let mut by_member: Vec<&Overload> = overloads.iter().collect();
+    by_member.sort_by_key(|overload| (overload.member.as_str(), overload.date));
+    for overload in by_member {
+        match periods.last_mut() {
+            Some((member, _, until, tasks))
+                if *member == overload.member
+                    && **tasks == overload.tasks
+                    && calendar.workdays_between(*until, overload.date) <= 1 =>
+            {
+                *until = overload.date;
+            }
+            _ => periods.push((
+                &overload.member,
+                overload.date,
+                overload.date,
+                &overload.tasks,
+            )),
+        }
+    }
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "Person".bold(),
+        "From".bold(),
+        "Until".bold(),
+        "Days".bold(),
+        "Tasks".bold()
+    ]);
+    for (member, from, until, tasks) in periods {
+        table.add_row(row![
+            titlecase(member),
+            dfmt(from, dates),
+            dfmt(until, dates),
+            (calendar.workdays_between(from, until) + 1).to_string(),
+            tasks
+                .iter()
+                .map(|id| format!("#{}", id))
+                .collect::<Vec<String>>()
+                .join(", ")
+                .red()
+        ]);
+    }
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::team;
+    use crate::project::{test_pool, TEST_PROJECT};
+
+    fn date(day: u32) -> NaiveDate {
+        // Monday 19.10.26
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn scheduled(id: i64, start_day: u32, finish_day: u32) -> ScheduledTask {
+        ScheduledTask {
+            id,
+            early_start: date(start_day),
+            early_finish: date(finish_day),
+            late_start: date(start_day),
+            late_finish: date(finish_day),
+            total_slack: 0,
+            free_slack: 0,
+        }
+    }
+
+    fn assignment(task_id: i64, member: &str, capacity: f64, calendar: &Calendar) -> Assignment {
+        Assignment {
+            task_id,
+            member: member.to_string(),
+            capacity,
+            calendar: calendar.clone(),
+        }
+    }
+
+    fn days(overloads: &[Overload]) -> Vec<(&str, NaiveDate, Vec<i64>)> {
+        overloads
+            .iter()
+            .map(|overload| {
+                (
+                    overload.member.as_str(),
+                    overload.date,
+                    overload.tasks.clone(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_days_with_overlapping_tasks() {
+        let calendar = Calendar::standard(&[]);
+        let assignments = vec![
+            assignment(1, "dana", 1.0, &calendar),
+            assignment(2, "dana", 1.0, &calendar),
+            assignment(3, "bob", 1.0, &calendar),
+        ];
+        let tasks = vec![
+            scheduled(1, 19, 21),
+            scheduled(2, 21, 26),
+            scheduled(3, 19, 23),
+        ];
+
+        assert_eq!(
+            days(&find_overloads(&assignments, &tasks)),
+            vec![("dana", date(21), vec![1, 2])]
+        );
+    }
+
+    #[test]
+    fn days_off_are_not_overloaded() {
+        let calendar = Calendar::standard(&[date(21)]);
+        let on_vacation = vec![
+            assignment(1, "dana", 1.0, &calendar),
+            assignment(2, "dana", 1.0, &calendar),
+        ];
+        let tasks = vec![scheduled(1, 19, 21), scheduled(2, 21, 26)];
+        assert!(find_overloads(&on_vacation, &tasks).is_empty());
+    }
+
+    #[test]
+    fn capacity_allows_concurrent_tasks() {
+        // Two full time people behind one name may work on two tasks at once, but not three
+        let calendar = Calendar::standard(&[]);
+        let team = vec![
+            assignment(1, "devs", 2.0, &calendar),
+            assignment(2, "devs", 2.0, &calendar),
+            assignment(3, "devs", 2.0, &calendar),
+        ];
+        let tasks = vec![
+            scheduled(1, 19, 20),
+            scheduled(2, 20, 21),
+            scheduled(3, 21, 22),
+        ];
+        assert!(find_overloads(&team, &tasks).is_empty());
+        let tasks = vec![
+            scheduled(1, 19, 21),
+            scheduled(2, 20, 21),
+            scheduled(3, 21, 22),
+        ];
+        assert_eq!(
+            days(&find_overloads(&team, &tasks)),
+            vec![("devs", date(21), vec![1, 2, 3])]
+        );
+    }
+
+    #[test]
+    fn weekends_are_not_overloaded() {
+        // Both tasks run over the weekend, only Friday and Monday clash
+        let calendar = Calendar::standard(&[]);
+        let assignments = vec![
+            assignment(1, "dana", 1.0, &calendar),
+            assignment(2, "dana", 1.0, &calendar),
+        ];
+        let tasks = vec![scheduled(1, 21, 26), scheduled(2, 23, 27)];
+        assert_eq!(
+            days(&find_overloads(&assignments, &tasks)),
+            vec![
+                ("dana", date(23), vec![1, 2]),
+                ("dana", date(26), vec![1, 2])
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn leveling_starts_over_from_the_plan() {
+        let pool = test_pool().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack,
+                               leveling_delay)
+            VALUES (1, 1, 'Start', 0, '2026-10-19', '2026-10-19', 0, 0),
+                   (1, 2, 'Design', 2, '2026-10-22', '2026-10-23', 4, 3),
+                   (1, 3, 'Build', 2, '2026-10-19', '2026-10-20', 4, 0),
+                   (1, 4, 'Test', 6, '2026-10-19', '2026-10-26', 0, 0),
+                   (1, 5, 'Finish', 0, '2026-10-27', '2026-10-27', 0, 0);
+            INSERT INTO dependencies (project_id, task_id, predecessor_id)
+            VALUES (1, 2, 1), (1, 3, 1), (1, 4, 1), (1, 5, 2), (1, 5, 3), (1, 5, 4);
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        team::add(&pool, "dana", &None, 1.0, 0.0).await.unwrap();
+        sqlx::query!(
+            r#"
+            INSERT INTO task_data (project_id, task_id, member_id)
+            SELECT 1, t.id, tm.id FROM tasks t, team tm WHERE t.id IN (2, 3)
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Without the delay of an earlier run Design and Build clash, holding back Build is
+        // enough and Design goes back to its planned start
+        check(&pool, TEST_PROJECT, &Config::default(), true, true)
+            .await
+            .unwrap();
+        let delays: Vec<(i64, i64)> = sqlx::query!(
+            r#"SELECT id as "id!", leveling_delay FROM tasks WHERE id IN (2, 3) ORDER BY id"#
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| (row.id, row.leveling_delay))
+        .collect();
+        assert_eq!(delays, vec![(2, 0), (3, 2)]);
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+use chrono::NaiveDate;
+use colored::Colorize;
+use prettytable::{row, Table};
+use sqlx::SqlitePool;
+
+// Snapshot the current dates, durations and slack of every task under the given name
+pub(crate) async fn save(pool: &SqlitePool, project_id: i64, name: &str) -> anyhow::Result<()> {
+    let existing = sqlx::query!(
+        r#"SELECT id FROM baselines WHERE project_id = ?1 AND name = ?2"#,
+        project_id,
+        name
+    )
+    .fetch_optional(pool)
+    .await?;
+    if existing.is_some() {
+        bail!("Baseline {} already exists", name);
+    }
+
+    let mut tx = pool.begin().await?;
+    let baseline_id = sqlx::query!(
+        r#"INSERT INTO baselines (project_id, name) VALUES (?1, ?2)"#,
+        project_id,
+        name
+    )
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    let tasks = sqlx::query!(
+        r#"
+        INSERT INTO baseline_tasks (baseline_id, task_id, duration, start_date, finish_date, total_slack)
+        SELECT ?1, id, duration, start_date, finish_date, total_slack
+        FROM tasks
+        WHERE project_id = ?2
+        "#,
+        baseline_id,
+        project_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    tx.commit().await?;
+
+    println!("📸 Saved baseline {} with {} tasks", name, tasks);
+    Ok(())
+}
+
+struct Summary {
+    name: String,
+    created_at: String,
+    tasks: i64,
+    finish_date: Option<String>,
+}
+
+pub(crate) async fn list(pool: &SqlitePool, project_id: i64) -> anyhow::Result<()> {
+    let mut table = Table::new();
+    table.add_row(row![
+        "Baseline".bold(),
+        "Created".bold(),
+        "Tasks".bold(),
+        "Planned Finish Date".bold(),
+    ]);
+    for baseline in summaries(pool, project_id).await? {
+        table.add_row(row![
+            baseline.name,
+            baseline.created_at,
+            baseline.tasks.to_string(),
+            baseline.finish_date.unwrap_or("--".to_string()),
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+async fn summaries(pool: &SqlitePool, project_id: i64) -> anyhow::Result<Vec<Summary>> {
+    let baselines = sqlx::query!(
+        r#"
+        SELECT b.name                as "name!",
+               b.created_at          as created_at,
+               count(bt.task_id)     as "tasks!: i64",
+               max(bt.finish_date)   as finish_date
+        FROM baselines b
+                 LEFT OUTER JOIN baseline_tasks bt
+                                 ON b.id = bt.baseline_id
+        WHERE b.project_id = ?1
+        GROUP BY b.id
+        ORDER BY b.created_at
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(baselines
+        .into_iter()
+        .map(|baseline| Summary {
+            name: baseline.name,
+            created_at: baseline.created_at.unwrap_or_default(),
+            tasks: baseline.tasks,
+            finish_date: baseline.finish_date,
+        })
+        .collect())
+}
+
+// Finish date of every task in the baseline
+pub(crate) async fn finish_dates(
+    pool: &SqlitePool,
+    project_id: i64,
+    name: &str,
+) -> anyhow::Result<HashMap<i64, NaiveDate>> {
+    let tasks = sqlx::query!(
+        r#"
+        SELECT bt.task_id as task_id, bt.finish_date as finish_date
+        FROM baseline_tasks bt
+                 JOIN baselines b ON b.id = bt.baseline_id
+        WHERE b.project_id = ?1
+          AND b.name = ?2
+        "#,
+        project_id,
+        name
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if tasks.is_empty() {
+        bail!("No baseline named {}", name);
+    }
+
+    let mut finish_dates = HashMap::new();
+    for task in tasks {
+        finish_dates.insert(task.task_id, task.finish_date.parse()?);
+    }
+    Ok(finish_dates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::calendar::Calendar;
+    use crate::project::{test_pool, TEST_PROJECT};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    async fn pool_with_tasks() -> SqlitePool {
+        let pool = test_pool().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack)
+            VALUES (1, 1, 'Design', 5, '2026-10-19', '2026-10-23', 0),
+                   (1, 2, 'Build', 3, '2026-10-26', '2026-10-28', 2)
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn keeps_the_finish_dates_of_the_snapshot() {
+        let pool = pool_with_tasks().await;
+        save(&pool, TEST_PROJECT, "v1").await.unwrap();
+        sqlx::query!(r#"UPDATE tasks SET finish_date = '2026-10-30' WHERE id = 2"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let finish_dates = finish_dates(&pool, TEST_PROJECT, "v1").await.unwrap();
+        assert_eq!(finish_dates, HashMap::from([(1, date(23)), (2, date(28))]));
+    }
+
+    #[tokio::test]
+    async fn names_are_unique() {
+        let pool = pool_with_tasks().await;
+        save(&pool, TEST_PROJECT, "v1").await.unwrap();
+
+        let error = save(&pool, TEST_PROJECT, "v1").await.err().unwrap();
+        assert_eq!(error.to_string(), "Baseline v1 already exists");
+    }
+
+    #[tokio::test]
+    async fn unknown_baseline_is_an_error() {
+        let pool = pool_with_tasks().await;
+
+        let error = finish_dates(&pool, TEST_PROJECT, "v1").await.err().unwrap();
+        assert_eq!(error.to_string(), "No baseline named v1");
+    }
+
+    #[tokio::test]
+    async fn lists_tasks_and_planned_finish_of_each_baseline() {
+        let pool = pool_with_tasks().await;
+        save(&pool, TEST_PROJECT, "v1").await.unwrap();
+        sqlx::query!(r#"DELETE FROM tasks WHERE id = 2"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+        save(&pool, TEST_PROJECT, "v2").await.unwrap();
+
+        let summaries = summaries(&pool, TEST_PROJECT).await.unwrap();
+        let summaries: Vec<(&str, i64, Option<&str>)> = summaries
+            .iter()
+            .map(|summary| {
+                (
+                    summary.name.as_str(),
+                    summary.tasks,
+                    summary.finish_date.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![("v1", 2, Some("2026-10-28")), ("v2", 1, Some("2026-10-23"))]
+        );
+    }
+
+    #[test]
+    fn variance_counts_working_days() {
+        // Fri 23 to Tue 27 slips two working days, the weekend does not count
+        let calendar = Calendar::standard(&[]);
+        assert_eq!(calendar.workdays_between(date(23), date(27)), 2);
+        assert_eq!(calendar.workdays_between(date(27), date(23)), -2);
+        assert_eq!(calendar.workdays_between(date(23), date(23)), 0);
+        // Nor does a holiday
+        let calendar = Calendar::standard(&[date(26)]);
+        assert_eq!(calendar.workdays_between(date(23), date(27)), 1);
+    }
+}
@@ -0,0 +1,776 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, bail};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use colored::Colorize;
+use prettytable::{row, Table};
+use sqlx::SqlitePool;
+
+use crate::config::Config;
+use crate::project::mspdi::PlanCalendar;
+use crate::project::{dfmt, schedule, team};
+
+const STANDARD_WEEKDAYS: &str = "Mon,Tue,Wed,Thu,Fri";
+
+// Working weekdays together with the holidays and vacations that are taken off
+#[derive(Clone, Debug)]
+pub(crate) struct Calendar {
+    weekdays: Vec<Weekday>,
+    days_off: BTreeSet<NaiveDate>,
+}
+
+impl Calendar {
+    pub(crate) fn is_workday(&self, date: NaiveDate) -> bool {
+        self.weekdays.contains(&date.weekday()) && !self.days_off.contains(&date)
+    }
+
+    pub(crate) fn workdays_per_week(&self) -> usize {
+        self.weekdays.len()
+    }
+
+    // Working days from one date to another, negative when `to` comes first
+    pub(crate) fn workdays_between(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        WorkDays::new(self, from).index(to)
+    }
+
+    // The working day `workdays` working days after the first working day on or after `date`
+    pub(crate) fn add_workdays(&self, date: NaiveDate, workdays: i64) -> NaiveDate {
+        WorkDays::new(self, date).date(workdays)
+    }
+}
+
+#[cfg(test)]
+impl Calendar {
+    // Monday to Friday with the given days off
+    pub(crate) fn standard(days_off: &[NaiveDate]) -> Self {
+        Calendar {
+            weekdays: parse_weekdays(STANDARD_WEEKDAYS).unwrap(),
+            days_off: days_off.iter().copied().collect(),
+        }
+    }
+}
+
+// Numbers working days relative to the first working day on or after the anchor date. Whole
+// weeks are counted at once and the days off in between are looked up, so dates a long way
+// from the anchor cost no more than close ones.
+pub(crate) struct WorkDays<'a> {
+    calendar: &'a Calendar,
+    anchor: NaiveDate,
+    // Days off on working weekdays in order, the others don't change the count
+    days_off: Vec<NaiveDate>,
+}
+
+impl<'a> WorkDays<'a> {
+    pub(crate) fn new(calendar: &'a Calendar, anchor: NaiveDate) -> Self {
+        let mut anchor = anchor;
+        while !calendar.is_workday(anchor) {
+            anchor += Duration::days(1);
+        }
+        let days_off = calendar
+            .days_off
+            .iter()
+            .filter(|date| calendar.weekdays.contains(&date.weekday()))
+            .copied()
+            .collect();
+        WorkDays {
+            calendar,
+            anchor,
+            days_off,
+        }
+    }
+
+    // Working days before the given date, a non-working day gets the number of the next working day
+    pub(crate) fn index(&self, date: NaiveDate) -> i64 {
+        self.weekdays_until(date) - self.days_off_until(date)
+    }
+
+    pub(crate) fn date(&self, index: i64) -> NaiveDate {
+        // Guess ignoring the days off and move on by the days off the guess came across
+        let mut weekday = index;
+        loop {
+            let date = self.nth_weekday(weekday);
+            let date_index = self.index(date);
+            if date_index != index {
+                weekday += index - date_index;
+            } else if self.days_off.binary_search(&date).is_ok() {
+                // The day off shares its number with the next working day
+                weekday += 1;
+            } else {
+                return date;
+            }
+        }
+    }
+
+    // Working weekdays from the anchor until the date, negative before it, as if nobody took
+    // days off
+    fn weekdays_until(&self, date: NaiveDate) -> i64 {
+        let (from, to, sign) = if date >= self.anchor {
+            (self.anchor, date, 1)
+        } else {
+            (date, self.anchor, -1)
+        };
+        let days = (to - from).num_days();
+        let weeks = days / 7;
+        let rest = (weeks * 7..days)
+            .filter(|day| {
+                let date = from + Duration::days(*day);
+                self.calendar.weekdays.contains(&date.weekday())
+            })
+            .count() as i64;
+        sign * (weeks * self.calendar.workdays_per_week() as i64 + rest)
+    }
+
+    // Days off from the anchor until the date, negative before it
+    fn days_off_until(&self, date: NaiveDate) -> i64 {
+        let position = |date: NaiveDate| self.days_off.partition_point(|day_off| *day_off < date);
+        position(date) as i64 - position(self.anchor) as i64
+    }
+
+    // The working weekday `n` working weekdays from the anchor, as if nobody took days off
+    fn nth_weekday(&self, n: i64) -> NaiveDate {
+        let per_week = self.calendar.workdays_per_week() as i64;
+        let step = Duration::days(n.signum());
+        let mut date = self.anchor + Duration::weeks(n / per_week);
+        let mut remaining = (n % per_week).abs();
+        while remaining > 0 {
+            date += step;
+            if self.calendar.weekdays.contains(&date.weekday()) {
+                remaining -= 1;
+            }
+        }
+        date
+    }
+
+    // Last day worked for a task running from `start` until the exclusive `finish`
+    pub(crate) fn finish_date(&self, start: i64, finish: i64) -> NaiveDate {
+        if finish > start {
+            self.date(finish - 1)
+        } else {
+            self.date(start)
+        }
+    }
+}
+
+// Calendar the scheduler works with, the project weekdays without public holidays
+pub(crate) async fn project_calendar(
+    pool: &SqlitePool,
+    project_id: i64,
+) -> anyhow::Result<Calendar> {
+    member_calendar(pool, project_id, None).await
+}
+
+// Calendar of a team member, their own weekdays if set and their vacations on top of the
+// project calendar
+pub(crate) async fn member_calendar(
+    pool: &SqlitePool,
+    project_id: i64,
+    member_id: Option<i64>,
+) -> anyhow::Result<Calendar> {
+    let weekdays = sqlx::query!(
+        r#"
+        SELECT work_weekdays
+        FROM calendars
+        WHERE project_id = ?1
+          AND (member_id = ?2 OR member_id IS NULL)
+        ORDER BY member_id IS NULL
+        LIMIT 1
+        "#,
+        project_id,
+        member_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .map(|row| row.work_weekdays)
+    .unwrap_or(STANDARD_WEEKDAYS.to_string());
+
+    let days_off = sqlx::query!(
+        r#"
+        SELECT date as "date!"
+        FROM calendar_days_off
+        WHERE project_id = ?1
+          AND (member_id IS NULL OR member_id = ?2)
+        "#,
+        project_id,
+        member_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut dates = BTreeSet::new();
+    for day_off in days_off {
+        dates.insert(day_off.date.parse()?);
+    }
+    Ok(Calendar {
+        weekdays: parse_weekdays(&weekdays)?,
+        days_off: dates,
+    })
+}
+
+// Calendars of everyone who was ever in the team, by member ID
+pub(crate) async fn member_calendars(
+    pool: &SqlitePool,
+    project_id: i64,
+) -> anyhow::Result<HashMap<i64, Calendar>> {
+    let members = sqlx::query!(r#"SELECT id as "id!" FROM team"#)
+        .fetch_all(pool)
+        .await?;
+
+    let mut calendars = HashMap::new();
+    for member in members {
+        calendars.insert(
+            member.id,
+            member_calendar(pool, project_id, Some(member.id)).await?,
+        );
+    }
+    Ok(calendars)
+}
+
+pub(crate) async fn show(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    member: &Option<String>,
+) -> anyhow::Result<()> {
+    let member = match member {
+        Some(member) => Some(team::find(pool, member).await?),
+        None => None,
+    };
+    let member_id = member.as_ref().map(|member| member.id);
+    let calendar = member_calendar(pool, project_id, member_id).await?;
+
+    println!(
+        "📆 {} works on {}",
+        member
+            .as_ref()
+            .map(|member| member.name.as_str())
+            .unwrap_or("The project"),
+        calendar
+            .weekdays
+            .iter()
+            .map(|weekday| weekday.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+
+    let days_off = sqlx::query!(
+        r#"
+        SELECT d.date        as "date!",
+               d.description as description,
+               tm.name       as "member?"
+        FROM calendar_days_off d
+                 LEFT JOIN team tm ON tm.id = d.member_id
+        WHERE d.project_id = ?1
+          AND (d.member_id IS NULL OR d.member_id = ?2 OR ?2 IS NULL)
+        ORDER BY d.date
+        "#,
+        project_id,
+        member_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if days_off.is_empty() {
+        println!("📆 No holidays or vacations");
+        return Ok(());
+    }
+    let mut table = Table::new();
+    table.add_row(row!["Date".bold(), "Who".bold(), "Description".bold()]);
+    for day_off in days_off {
+        table.add_row(row![
+            dfmt(day_off.date.parse()?, &config.dates),
+            day_off.member.unwrap_or("Everyone".to_string()),
+            day_off.description.unwrap_or_default()
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+pub(crate) async fn set_weekdays(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    weekdays: &str,
+    member: &Option<String>,
+) -> anyhow::Result<()> {
+    let work_weekdays = format_weekdays(&parse_weekdays(weekdays)?);
+
+    match member {
+        Some(member) => {
+            let member = team::find(pool, member).await?;
+            sqlx::query!(
+                r#"
+                INSERT INTO calendars (project_id, member_id, work_weekdays) VALUES (?1, ?2, ?3)
+                ON CONFLICT (project_id, member_id)
+                    DO UPDATE SET work_weekdays = excluded.work_weekdays
+                "#,
+                project_id,
+                member.id,
+                work_weekdays
+            )
+            .execute(pool)
+            .await?;
+            println!(
+                "📆 {} works on {}",
+                member.name,
+                work_weekdays.replace(',', ", ")
+            );
+        }
+        None => {
+            save_project_weekdays(pool, project_id, &work_weekdays).await?;
+            println!(
+                "📆 The project works on {}",
+                work_weekdays.replace(',', ", ")
+            );
+        }
+    }
+    schedule::update(pool, project_id, config).await?;
+    Ok(())
+}
+
+// The project calendar has no member to conflict on, projects created after the calendars
+// came along start without one
+async fn save_project_weekdays(
+    pool: &SqlitePool,
+    project_id: i64,
+    work_weekdays: &str,
+) -> anyhow::Result<()> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE calendars
+        SET work_weekdays = ?2
+        WHERE project_id = ?1
+          AND member_id IS NULL
+        "#,
+        project_id,
+        work_weekdays
+    )
+    .execute(pool)
+    .await?;
+    if updated.rows_affected() == 0 {
+        sqlx::query!(
+            r#"INSERT INTO calendars (project_id, work_weekdays) VALUES (?1, ?2)"#,
+            project_id,
+            work_weekdays
+        )
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
+// The weekdays of an imported plan replace the project's, its days off are taken on top of
+// the holidays already known
+pub(crate) async fn import_plan_calendar(
+    pool: &SqlitePool,
+    project_id: i64,
+    plan_calendar: &PlanCalendar,
+) -> anyhow::Result<()> {
+    let work_weekdays = format_weekdays(&plan_calendar.weekdays);
+    save_project_weekdays(pool, project_id, &work_weekdays).await?;
+    for (from, to, name) in &plan_calendar.days_off {
+        let description = Some(name.clone()).filter(|name| !name.is_empty());
+        add_days_off(pool, project_id, None, *from, *to, &description).await?;
+    }
+    println!(
+        "📆 The plan works on {} and takes {} days off",
+        work_weekdays.replace(',', ", "),
+        plan_calendar
+            .days_off
+            .iter()
+            .map(|(from, to, _)| (*to - *from).num_days() + 1)
+            .sum::<i64>()
+    );
+    Ok(())
+}
+
+pub(crate) async fn holiday(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    date: NaiveDate,
+    description: &Option<String>,
+    remove: bool,
+) -> anyhow::Result<()> {
+    if remove {
+        remove_days_off(pool, project_id, None, date, date).await?;
+        println!("📆 {} is a working day again", dfmt(date, &config.dates));
+    } else {
+        add_days_off(pool, project_id, None, date, date, description).await?;
+        println!("🎉 {} is a holiday", dfmt(date, &config.dates));
+    }
+    schedule::update(pool, project_id, config).await?;
+    Ok(())
+}
+
+pub(crate) async fn vacation(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    member: &str,
+    from: NaiveDate,
+    to: Option<NaiveDate>,
+    remove: bool,
+) -> anyhow::Result<()> {
+    let member = team::find(pool, member).await?;
+    let to = to.unwrap_or(from);
+    if to < from {
+        bail!("Vacation ends before it starts");
+    }
+
+    if remove {
+        remove_days_off(pool, project_id, Some(member.id), from, to).await?;
+        println!("📆 Cancelled the vacation of {}", member.name);
+    } else {
+        add_days_off(
+            pool,
+            project_id,
+            Some(member.id),
+            from,
+            to,
+            &Some("Vacation".to_string()),
+        )
+        .await?;
+        println!(
+            "🏖️ {} is on vacation from {} until {}",
+            member.name,
+            dfmt(from, &config.dates),
+            dfmt(to, &config.dates)
+        );
+    }
+    schedule::update(pool, project_id, config).await?;
+    Ok(())
+}
+
+// Public holidays from an iCal (.ics) file, every day of every event is taken off
+pub(crate) async fn import_holidays(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow!("Could not read {}: {}", path.display(), e))?;
+    let events = parse_ical(&content)?;
+    if events.is_empty() {
+        bail!("No events found in {}", path.display());
+    }
+
+    let mut days = 0;
+    for (start, end, summary) in &events {
+        let last = (*end - Duration::days(1)).max(*start);
+        add_days_off(pool, project_id, None, *start, last, &Some(summary.clone())).await?;
+        days += (last - *start).num_days() + 1;
+    }
+    println!(
+        "🎉 Imported {} holidays ({} days) from {}",
+        events.len(),
+        days,
+        path.display()
+    );
+    schedule::update(pool, project_id, config).await?;
+    Ok(())
+}
+
+async fn add_days_off(
+    pool: &SqlitePool,
+    project_id: i64,
+    member_id: Option<i64>,
+    from: NaiveDate,
+    to: NaiveDate,
+    description: &Option<String>,
+) -> anyhow::Result<()> {
+    // Replacing keeps a day from being taken off twice
+    remove_days_off(pool, project_id, member_id, from, to).await?;
+    let mut date = from;
+    while date <= to {
+        let date_string = date.to_string();
+        sqlx::query!(
+            r#"
+            INSERT INTO calendar_days_off (project_id, member_id, date, description)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+            project_id,
+            member_id,
+            date_string,
+            description
+        )
+        .execute(pool)
+        .await?;
+        date += Duration::days(1);
+    }
+    Ok(())
+}
+
+async fn remove_days_off(
+    pool: &SqlitePool,
+    project_id: i64,
+    member_id: Option<i64>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> anyhow::Result<()> {
+    let from = from.to_string();
+    let to = to.to_string();
+    sqlx::query!(
+        r#"
+        DELETE FROM calendar_days_off
+        WHERE project_id = ?1
+          AND member_id IS ?2
+          AND date BETWEEN ?3 AND ?4
+        "#,
+        project_id,
+        member_id,
+        from,
+        to
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// Weekdays like "Mon,Tue,Wed,Thu" or "monday, tuesday"
+fn parse_weekdays(weekdays: &str) -> anyhow::Result<Vec<Weekday>> {
+    let mut parsed = vec![];
+    for weekday in weekdays.split(',').map(str::trim).filter(|w| !w.is_empty()) {
+        let weekday: Weekday = weekday
+            .parse()
+            .map_err(|_| anyhow!("Unknown weekday {}", weekday))?;
+        if !parsed.contains(&weekday) {
+            parsed.push(weekday);
+        }
+    }
+    if parsed.is_empty() {
+        bail!("A calendar needs at least one working weekday");
+    }
+    parsed.sort_by_key(|weekday| weekday.num_days_from_monday());
+    Ok(parsed)
+}
+
+fn format_weekdays(weekdays: &[Weekday]) -> String {
+    weekdays
+        .iter()
+        .map(|weekday| weekday.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// Start, exclusive end and summary of the events in an iCal file. Only the dates are used,
+// events without an end take a single day.
+fn parse_ical(content: &str) -> anyhow::Result<Vec<(NaiveDate, NaiveDate, String)>> {
+    // Long lines are folded onto continuation lines starting with a space
+    let unfolded = content
+        .replace("\r\n", "\n")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+
+    let mut events = vec![];
+    let mut event: Option<(Option<NaiveDate>, Option<NaiveDate>, String)> = None;
+    for line in unfolded.lines() {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Properties carry parameters after a semicolon, e.g. DTSTART;VALUE=DATE
+        let name = name.split(';').next().unwrap_or_default();
+        match (name, &mut event) {
+            ("BEGIN", _) if value == "VEVENT" => event = Some((None, None, String::new())),
+            ("END", Some((start, end, summary))) if value == "VEVENT" => {
+                let start = start.ok_or(anyhow!("Event {} has no start date", summary))?;
+                let end = end.unwrap_or(start + Duration::days(1));
+                events.push((start, end, summary.clone()));
+                event = None;
+            }
+            ("DTSTART", Some((start, _, _))) => *start = Some(parse_ical_date(value)?),
+            ("DTEND", Some((_, end, _))) => *end = Some(parse_ical_date(value)?),
+            ("SUMMARY", Some((_, _, summary))) => *summary = value.replace("\\,", ","),
+            _ => {}
+        }
+    }
+    Ok(events)
+}
+
+fn parse_ical_date(value: &str) -> anyhow::Result<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..8).unwrap_or(value), "%Y%m%d")
+        .map_err(|_| anyhow!("Invalid iCal date {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{projects, test_pool, TEST_PROJECT};
+
+    fn date(day: u32) -> NaiveDate {
+        // Monday 19.10.26
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn parses_weekdays_in_order_once() {
+        assert_eq!(
+            parse_weekdays("Wed, mon,Tue,Mon,").unwrap(),
+            vec![Weekday::Mon, Weekday::Tue, Weekday::Wed]
+        );
+        assert!(parse_weekdays("Mon,Funday").is_err());
+        assert!(parse_weekdays(" , ").is_err());
+    }
+
+    #[test]
+    fn weekends_and_days_off_are_not_worked() {
+        let calendar = Calendar::standard(&[date(21)]);
+        assert!(calendar.is_workday(date(20)));
+        assert!(!calendar.is_workday(date(21)));
+        assert!(!calendar.is_workday(date(24)));
+        assert!(!calendar.is_workday(date(25)));
+        assert_eq!(calendar.workdays_per_week(), 5);
+    }
+
+    #[test]
+    fn numbers_working_days_from_the_anchor() {
+        let calendar = Calendar::standard(&[date(21)]);
+        // A Saturday anchor starts counting on Monday
+        let days = WorkDays::new(&calendar, date(17));
+        assert_eq!(days.index(date(19)), 0);
+        assert_eq!(days.index(date(22)), 2);
+        // Non-working days get the number of the next working day
+        assert_eq!(days.index(date(21)), 2);
+        assert_eq!(days.index(date(24)), 4);
+        assert_eq!(days.index(date(16)), -1);
+
+        assert_eq!(days.date(0), date(19));
+        assert_eq!(days.date(2), date(22));
+        assert_eq!(days.date(4), date(26));
+        assert_eq!(days.date(-1), date(16));
+        for index in -3..10 {
+            assert_eq!(days.index(days.date(index)), index);
+        }
+    }
+
+    #[test]
+    fn numbers_match_counting_day_by_day() {
+        let days_off = [date(21), date(22), date(24), date(30)];
+        let mut calendar = Calendar::standard(&days_off);
+        calendar.weekdays = parse_weekdays("Mon,Wed,Thu,Sat").unwrap();
+        let days = WorkDays::new(&calendar, date(20));
+        assert_eq!(days.date(0), date(26));
+
+        let mut index = 0;
+        let mut day = date(26);
+        while day < date(26) + Duration::days(120) {
+            if calendar.is_workday(day) {
+                assert_eq!(days.date(index), day);
+                index += 1;
+            }
+            day += Duration::days(1);
+            assert_eq!(days.index(day), index, "{}", day);
+        }
+        let mut index = 0;
+        let mut day = date(26);
+        while day > date(26) - Duration::days(120) {
+            day -= Duration::days(1);
+            if calendar.is_workday(day) {
+                index -= 1;
+                assert_eq!(days.date(index), day);
+            }
+            assert_eq!(days.index(day), index, "{}", day);
+        }
+    }
+
+    #[test]
+    fn finish_date_is_the_last_day_worked() {
+        let calendar = Calendar::standard(&[]);
+        let days = WorkDays::new(&calendar, date(19));
+        assert_eq!(days.finish_date(0, 5), date(23));
+        assert_eq!(days.finish_date(3, 4), date(22));
+        // Milestones finish on the day they start
+        assert_eq!(days.finish_date(5, 5), date(26));
+    }
+
+    #[test]
+    fn adds_and_counts_working_days() {
+        let calendar = Calendar::standard(&[date(26)]);
+        assert_eq!(calendar.add_workdays(date(23), 1), date(27));
+        assert_eq!(calendar.add_workdays(date(24), 0), date(27));
+        assert_eq!(calendar.workdays_between(date(19), date(28)), 6);
+    }
+
+    #[tokio::test]
+    async fn every_project_keeps_its_own_calendar() {
+        let pool = test_pool().await;
+        projects::new(&pool, "Second").await.unwrap();
+        let second = projects::find(&pool, "Second").await.unwrap().id;
+        let config = Config::default();
+
+        holiday(&pool, TEST_PROJECT, &config, date(21), &None, false)
+            .await
+            .unwrap();
+        set_weekdays(&pool, second, &config, "Mon,Tue,Wed,Thu", &None)
+            .await
+            .unwrap();
+
+        let first = project_calendar(&pool, TEST_PROJECT).await.unwrap();
+        assert!(!first.is_workday(date(21)));
+        assert!(first.is_workday(date(23)));
+        let second = project_calendar(&pool, second).await.unwrap();
+        assert!(second.is_workday(date(21)));
+        assert!(!second.is_workday(date(23)));
+    }
+
+    #[tokio::test]
+    async fn imported_plans_bring_their_calendar() {
+        let pool = test_pool().await;
+        let plan_calendar = PlanCalendar {
+            weekdays: parse_weekdays("Tue,Wed,Thu,Fri,Sat").unwrap(),
+            days_off: vec![(date(22), date(23), "Fair".to_string())],
+        };
+        import_plan_calendar(&pool, TEST_PROJECT, &plan_calendar)
+            .await
+            .unwrap();
+
+        let calendar = project_calendar(&pool, TEST_PROJECT).await.unwrap();
+        let worked: Vec<u32> = (19..=25)
+            .filter(|day| calendar.is_workday(date(*day)))
+            .collect();
+        assert_eq!(worked, vec![20, 21, 24]);
+    }
+
+    #[test]
+    fn reads_all_day_events_from_ical() {
+        let content = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART;VALUE=DATE:20261226\r\n\
+            DTEND;VALUE=DATE:20261228\r\n\
+            SUMMARY:Christmas\\, Boxing\r\n  Day\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART:20261231T090000Z\r\n\
+            SUMMARY:New Year's Eve\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+        let christmas = NaiveDate::from_ymd_opt(2026, 12, 26).unwrap();
+        let new_years_eve = NaiveDate::from_ymd_opt(2026, 12, 31).unwrap();
+
+        assert_eq!(
+            parse_ical(content).unwrap(),
+            vec![
+                (
+                    christmas,
+                    christmas + Duration::days(2),
+                    "Christmas, Boxing Day".to_string()
+                ),
+                (
+                    new_years_eve,
+                    new_years_eve + Duration::days(1),
+                    "New Year's Eve".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn refuses_ical_events_without_a_start() {
+        let content = "BEGIN:VEVENT\nSUMMARY:Someday\nEND:VEVENT\n";
+        assert!(parse_ical(content).is_err());
+        assert!(parse_ical("BEGIN:VEVENT\nDTSTART:2026\nEND:VEVENT\n").is_err());
+    }
+}
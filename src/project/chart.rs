@@ -0,0 +1,209 @@
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use clap::ValueEnum;
+use inquire::{Confirm, Select};
+use plotly::{ImageFormat, Plot};
+use promptly::prompt_default;
+use serde::Deserialize;
+
+use crate::config::ChartSettings;
+
+#[derive(Clone, Copy, Debug, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ChartFormat {
+    Pdf,
+    Svg,
+    Png,
+    /// Standalone page that can be zoomed and hovered in a browser
+    Html,
+}
+
+impl ChartFormat {
+    // Images are rendered by kaleido, HTML is written by plotly itself
+    fn image_format(self) -> Option<ImageFormat> {
+        match self {
+            ChartFormat::Pdf => Some(ImageFormat::PDF),
+            ChartFormat::Svg => Some(ImageFormat::SVG),
+            ChartFormat::Png => Some(ImageFormat::PNG),
+            ChartFormat::Html => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            ChartFormat::Pdf => "pdf",
+            ChartFormat::Svg => "svg",
+            ChartFormat::Png => "png",
+            ChartFormat::Html => "html",
+        }
+    }
+}
+
+impl fmt::Display for ChartFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension().to_uppercase())
+    }
+}
+
+// Where to write a chart, anything left out is prompted for
+pub(crate) struct ChartOutput {
+    pub(crate) format: Option<ChartFormat>,
+    pub(crate) out_file: Option<PathBuf>,
+    pub(crate) open: bool,
+    pub(crate) settings: ChartSettings,
+}
+
+pub(crate) fn export_chart(
+    plot: &Plot,
+    output: &ChartOutput,
+    file_prefix: &str,
+    chart_name: &str,
+) -> anyhow::Result<()> {
+    // Use the format given, the one matching the output file extension or the configured one
+    let format = output
+        .format
+        .or_else(|| {
+            output
+                .out_file
+                .as_ref()
+                .and_then(|out_file| out_file.extension())
+                .and_then(|extension| {
+                    ChartFormat::from_str(&extension.to_string_lossy(), true).ok()
+                })
+        })
+        .or(output.settings.format);
+
+    let format = match format {
+        Some(format) => format,
+        None => {
+            let options = ChartFormat::value_variants().to_vec();
+            let ans = Select::new("Output file format?", options).prompt();
+
+            match ans {
+                Ok(format) => format,
+                Err(_) => {
+                    println!("Could not get output file format, defaulting to PDF");
+                    ChartFormat::Pdf
+                }
+            }
+        }
+    };
+
+    let out_file: PathBuf = match &output.out_file {
+        Some(out_file) => out_file.clone(),
+        None => {
+            // Generate outfile
+            let today = Local::now();
+            let prefixed_file_name = format!(
+                "{}-week-{}-({}).{}",
+                file_prefix,
+                today.iso_week().week(),
+                today.format("%s"),
+                format.extension()
+            );
+
+            let path = output.settings.folder.join(prefixed_file_name);
+            prompt_default("Enter path to generated chart:", path)?
+        }
+    };
+    if let Some(parent) = out_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    match format.image_format() {
+        Some(image_format) => plot.write_image(
+            out_file.clone(),
+            image_format,
+            output.settings.width,
+            output.settings.height,
+            1.0,
+        ),
+        None => plot.write_html(out_file.clone()),
+    };
+
+    // Only ask to open the chart when running interactively
+    let open = if output.open || output.out_file.is_some() {
+        output.open
+    } else {
+        let ans = Confirm::new(&format!("Open the generated {}?", chart_name))
+            .with_default(false)
+            .prompt();
+        ans.is_ok() && ans.unwrap()
+    };
+
+    let file = out_file.clone();
+
+    if open {
+        opener::open(file).expect("Could not open file");
+    } else {
+        println!("Ok, the chart is saved in {}", file.to_str().unwrap());
+    }
+
+    // To open in browser
+    // plot.show()
+    Ok(())
+}
+
+pub(crate) fn generate_week_numbers(start_date: NaiveDate, finish_date: NaiveDate) -> Vec<i32> {
+    let mut week_numbers = Vec::new();
+    let mut current_date = start_date;
+
+    while current_date <= finish_date {
+        week_numbers.push(week_number(current_date));
+        current_date += Duration::weeks(1);
+    }
+    week_numbers
+}
+
+// ISO year and week of a date, e.g. 202342, so the days around new year land in one week
+pub(crate) fn week_number(date: NaiveDate) -> i32 {
+    let week = date.iso_week();
+    week.year() * 100 + week.week() as i32
+}
+
+// Axis labels for the weeks, e.g. W2342
+pub(crate) fn week_labels(week_numbers: &[i32]) -> Vec<String> {
+    let week_prefix = "W";
+    week_numbers
+        .iter()
+        .map(|&num| week_prefix.to_owned() + num.to_string().get(2..).unwrap_or_default())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weeks_are_numbered_by_year_and_week() {
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        assert_eq!(week_number(monday), 202643);
+        assert_eq!(week_number(monday + Duration::days(6)), 202643);
+        assert_eq!(
+            generate_week_numbers(monday, monday + Duration::days(15)),
+            vec![202643, 202644, 202645]
+        );
+        assert_eq!(
+            week_labels(&[202609, 202643]),
+            vec!["W2609".to_string(), "W2643".to_string()]
+        );
+    }
+
+    #[test]
+    fn weeks_around_new_year_belong_to_the_iso_year() {
+        let new_years_eve = NaiveDate::from_ymd_opt(2026, 12, 31).unwrap();
+        assert_eq!(week_number(new_years_eve), 202653);
+        assert_eq!(week_number(new_years_eve + Duration::days(1)), 202653);
+        assert_eq!(
+            week_number(NaiveDate::from_ymd_opt(2024, 12, 30).unwrap()),
+            202501
+        );
+        assert_eq!(
+            generate_week_numbers(new_years_eve, new_years_eve + Duration::days(7)),
+            vec![202653, 202701]
+        );
+    }
+}
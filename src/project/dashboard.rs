@@ -0,0 +1,605 @@
+use std::collections::HashMap;
+
+use chrono::Local;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Clear, List, ListState, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use sqlx::SqlitePool;
+use titlecase::titlecase;
+
+use crate::config::Config;
+use crate::project::dependency::{self, Link};
+use crate::project::{
+    assign_tasks, complete_tasks, dfmt, get_tasks, list_cells, log_work, slack_color, team, Task,
+    TaskStatus, LIST_COLUMNS,
+};
+
+// Days that can be logged at once, as offered by `log`
+const LOG_DAYS: [i32; 5] = [5, 4, 3, 2, 1];
+
+const HELP: &str = "↑↓ select  a assign  c complete  l log time  s status  r refresh  q quit";
+
+enum Popup {
+    Assign(ListState),
+    Complete,
+    Log(ListState),
+}
+
+struct Dashboard<'a> {
+    pool: &'a SqlitePool,
+    project_id: i64,
+    config: &'a Config,
+    status: TaskStatus,
+    tasks: Vec<Task>,
+    // Names and successors of every task, including milestones and tasks filtered out
+    names: HashMap<i64, String>,
+    // Links of each successor to the task, by successor
+    successors: HashMap<i64, Vec<(i64, Link)>>,
+    members: Vec<team::Member>,
+    table: TableState,
+    popup: Option<Popup>,
+    message: Option<Line<'static>>,
+}
+
+// Task table with details of the selected task, the tasks are assigned, completed and
+// logged on without leaving it
+pub(crate) async fn run(pool: &SqlitePool, project_id: i64, config: &Config) -> anyhow::Result<()> {
+    let mut dashboard = Dashboard::new(pool, project_id, config);
+    dashboard.refresh().await?;
+
+    let mut terminal = ratatui::init();
+    let result = dashboard.run(&mut terminal).await;
+    ratatui::restore();
+    result
+}
+
+impl<'a> Dashboard<'a> {
+    fn new(pool: &'a SqlitePool, project_id: i64, config: &'a Config) -> Self {
+        Dashboard {
+            pool,
+            project_id,
+            config,
+            status: TaskStatus::Pending,
+            tasks: vec![],
+            names: HashMap::new(),
+            successors: HashMap::new(),
+            members: vec![],
+            table: TableState::default(),
+            popup: None,
+            message: None,
+        }
+    }
+
+    async fn run(&mut self, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            let changed = match self.popup.take() {
+                Some(popup) => self.handle_popup(popup, key.code).await?,
+                None => match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        self.table.select_next();
+                        false
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        self.table.select_previous();
+                        false
+                    }
+                    KeyCode::Char('s') => {
+                        self.status = next_status(self.status);
+                        self.table.select_first();
+                        true
+                    }
+                    KeyCode::Char('r') => true,
+                    KeyCode::Char('a') => self.open(Popup::Assign(ListState::default())),
+                    KeyCode::Char('c') => self.open(Popup::Complete),
+                    KeyCode::Char('l') => self.open(Popup::Log(ListState::default())),
+                    _ => false,
+                },
+            };
+
+            // The commands report on stdout, redraw everything on top of it
+            if changed {
+                self.refresh().await?;
+                terminal.clear()?;
+            }
+        }
+    }
+
+    fn open(&mut self, popup: Popup) -> bool {
+        if self.selected().is_none() {
+            self.message = Some(Line::from("No task selected").red());
+            return false;
+        }
+        let popup = match popup {
+            Popup::Assign(mut state) => {
+                if self.members.is_empty() {
+                    self.message =
+                        Some(Line::from("No team members yet, add them with `team add`").red());
+                    return false;
+                }
+                state.select_first();
+                Popup::Assign(state)
+            }
+            Popup::Log(mut state) => {
+                state.select_last();
+                Popup::Log(state)
+            }
+            Popup::Complete => Popup::Complete,
+        };
+        self.popup = Some(popup);
+        self.message = None;
+        false
+    }
+
+    // Returns whether the tasks changed
+    async fn handle_popup(&mut self, popup: Popup, key: KeyCode) -> anyhow::Result<bool> {
+        let Some(task_id) = self.selected().map(|task| task.id) else {
+            return Ok(false);
+        };
+        let result = match (popup, key) {
+            (_, KeyCode::Esc) | (Popup::Complete, KeyCode::Char('n')) => return Ok(false),
+            (Popup::Assign(mut state), KeyCode::Up | KeyCode::Down | KeyCode::Char('j' | 'k')) => {
+                move_selection(&mut state, key);
+                self.popup = Some(Popup::Assign(state));
+                return Ok(false);
+            }
+            (Popup::Log(mut state), KeyCode::Up | KeyCode::Down | KeyCode::Char('j' | 'k')) => {
+                move_selection(&mut state, key);
+                self.popup = Some(Popup::Log(state));
+                return Ok(false);
+            }
+            (Popup::Assign(state), KeyCode::Enter | KeyCode::Char('f')) => {
+                let member =
+                    &self.members[state.selected().unwrap_or(0).min(self.members.len() - 1)];
+                // Assigning against the planned role needs to be forced
+                let force = key == KeyCode::Char('f');
+                assign_tasks(
+                    self.pool.clone(),
+                    self.project_id,
+                    self.config,
+                    &[task_id],
+                    Some(member.name.clone()),
+                    force,
+                )
+                .await
+                .map(|_| format!("Assigned #{} to {}", task_id, member.name))
+            }
+            (Popup::Complete, KeyCode::Enter | KeyCode::Char('y')) => {
+                complete_tasks(self.pool, self.project_id, self.config, &[task_id], None)
+                    .await
+                    .map(|_| format!("Completed #{}", task_id))
+            }
+            (Popup::Log(state), KeyCode::Enter) => {
+                let days = LOG_DAYS[state.selected().unwrap_or(0).min(LOG_DAYS.len() - 1)];
+                let today = Local::now().date_naive();
+                log_work(
+                    self.pool,
+                    self.project_id,
+                    self.config,
+                    Some(task_id),
+                    Some(today),
+                    Some(days),
+                    None,
+                )
+                .await
+                .map(|_| format!("Logged {} days on #{}", days, task_id))
+            }
+            (popup, _) => {
+                self.popup = Some(popup);
+                return Ok(false);
+            }
+        };
+        self.message = Some(match result {
+            Ok(message) => Line::from(format!("✅ {}", message)),
+            Err(e) => Line::from(e.to_string()).red(),
+        });
+        Ok(true)
+    }
+
+    async fn refresh(&mut self) -> anyhow::Result<()> {
+        let selected = self.selected().map(|task| task.id);
+        self.tasks = get_tasks(self.pool.clone(), self.project_id, self.status).await?;
+        self.members = team::members(self.pool).await?;
+
+        let rows = sqlx::query!(
+            r#"SELECT id as "id!", name FROM tasks WHERE project_id = ?1"#,
+            self.project_id
+        )
+        .fetch_all(self.pool)
+        .await?;
+        self.names = rows.into_iter().map(|row| (row.id, row.name)).collect();
+        self.successors.clear();
+        for (id, links) in dependency::load(self.pool, self.project_id).await? {
+            for link in links {
+                self.successors
+                    .entry(link.predecessor)
+                    .or_default()
+                    .push((id, link));
+            }
+        }
+
+        // Stay on the same task when it is still listed
+        let index = selected
+            .and_then(|id| self.tasks.iter().position(|task| task.id == id))
+            .or(self.table.selected())
+            .map(|index| index.min(self.tasks.len().saturating_sub(1)));
+        self.table
+            .select(index.or(Some(0)).filter(|_| !self.tasks.is_empty()));
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<&Task> {
+        self.table
+            .selected()
+            .and_then(|index| self.tasks.get(index))
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tasks_area, details_area, help_area] = Layout::vertical([
+            Constraint::Min(5),
+            Constraint::Length(8),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let header = Row::new(LIST_COLUMNS.iter().map(|column| Cell::from(*column)))
+            .style(Style::new().bold());
+        let rows = self.tasks.iter().map(|task| {
+            let cells = list_cells(task, &self.config.dates)
+                .into_iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    let cell = Cell::from(cell);
+                    match i {
+                        2 if task.assignee.is_some() && !task.finished => cell.blue(),
+                        4 if !task.finished => {
+                            cell.fg(terminal_color(slack_color(task.slack, &self.config.slack)))
+                        }
+                        _ => cell,
+                    }
+                });
+            let row = Row::new(cells);
+            if task.finished {
+                row.dark_gray()
+            } else {
+                row
+            }
+        });
+        let widths = LIST_COLUMNS.iter().enumerate().map(|(i, column)| match i {
+            2 => Constraint::Min(20),
+            _ => Constraint::Length(column.chars().count().max(5) as u16),
+        });
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::bordered().title(format!(" Tasks ({:?}) ", self.status)))
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, tasks_area, &mut self.table);
+
+        frame.render_widget(
+            Paragraph::new(self.details()).block(Block::bordered().title(" Details ")),
+            details_area,
+        );
+
+        let help = match &self.message {
+            Some(message) => message.clone(),
+            None => Line::from(HELP).dark_gray(),
+        };
+        frame.render_widget(help, help_area);
+
+        if let Some(popup) = &mut self.popup {
+            let task = self
+                .table
+                .selected()
+                .and_then(|index| self.tasks.get(index))
+                .map(|task| format!("#{} {}", task.id, task.name))
+                .unwrap_or_default();
+            match popup {
+                Popup::Assign(state) => {
+                    let area = popup_area(frame.area(), 50, self.members.len() as u16 + 2);
+                    let members = self.members.iter().map(|member| {
+                        format!(
+                            "{} ({})",
+                            member.name,
+                            member.role.as_deref().unwrap_or("no role")
+                        )
+                    });
+                    frame.render_widget(Clear, area);
+                    frame.render_stateful_widget(
+                        List::new(members)
+                            .block(
+                                Block::bordered()
+                                    .title(format!(" Assign {} (f to force the role) ", task)),
+                            )
+                            .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+                        area,
+                        state,
+                    );
+                }
+                Popup::Log(state) => {
+                    let area = popup_area(frame.area(), 40, LOG_DAYS.len() as u16 + 2);
+                    let days = LOG_DAYS.iter().map(|days| format!("{} days today", days));
+                    frame.render_widget(Clear, area);
+                    frame.render_stateful_widget(
+                        List::new(days)
+                            .block(Block::bordered().title(format!(" Log work on {} ", task)))
+                            .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+                        area,
+                        state,
+                    );
+                }
+                Popup::Complete => {
+                    let area = popup_area(frame.area(), 50, 3);
+                    frame.render_widget(Clear, area);
+                    frame.render_widget(
+                        Paragraph::new(format!("Complete {}? (y/n)", task))
+                            .block(Block::bordered()),
+                        area,
+                    );
+                }
+            }
+        }
+    }
+
+    fn details(&self) -> Vec<Line<'static>> {
+        let Some(task) = self.selected() else {
+            return vec![Line::from("No tasks")];
+        };
+        // Other task of each link, with the link type and lag unless it is a plain finish to start
+        let links = |links: Vec<(i64, Link)>| {
+            if links.is_empty() {
+                return "--".to_string();
+            }
+            links
+                .iter()
+                .map(|(id, link)| {
+                    let name = match self.names.get(id) {
+                        Some(name) => format!("#{} {}", id, name),
+                        None => format!("#{}", id),
+                    };
+                    match link.type_and_lag() {
+                        type_and_lag if type_and_lag.is_empty() => name,
+                        type_and_lag => format!("{} ({})", name, type_and_lag),
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        vec![
+            Line::from(format!("#{} {}", task.id, task.name).bold()),
+            Line::from(format!(
+                "Assignee: {}   Planned for: {}",
+                task.assignee
+                    .as_deref()
+                    .map(titlecase)
+                    .unwrap_or("--".to_string()),
+                task.resource_names.join(",")
+            )),
+            Line::from(vec![
+                Span::raw(format!(
+                    "{} → {}, {} days, {} % done, slack ",
+                    dfmt(task.start_date, &self.config.dates),
+                    dfmt(task.finish_date, &self.config.dates),
+                    task.duration,
+                    task.percent_complete
+                )),
+                Span::raw(task.slack.to_string())
+                    .fg(terminal_color(slack_color(task.slack, &self.config.slack))),
+            ]),
+            Line::from(format!(
+                "Predecessors: {}",
+                links(
+                    task.predecessors
+                        .iter()
+                        .map(|link| (link.predecessor, *link))
+                        .collect()
+                )
+            )),
+            Line::from(format!(
+                "Successors: {}",
+                links(self.successors.get(&task.id).cloned().unwrap_or_default())
+            )),
+        ]
+    }
+}
+
+fn move_selection(state: &mut ListState, key: KeyCode) {
+    if matches!(key, KeyCode::Up | KeyCode::Char('k')) {
+        state.select_previous();
+    } else {
+        state.select_next();
+    }
+}
+
+fn next_status(status: TaskStatus) -> TaskStatus {
+    match status {
+        TaskStatus::Pending => TaskStatus::Assigned,
+        TaskStatus::Assigned => TaskStatus::Unassigned,
+        TaskStatus::Unassigned => TaskStatus::Completed,
+        TaskStatus::Completed => TaskStatus::All,
+        TaskStatus::All => TaskStatus::Pending,
+    }
+}
+
+fn terminal_color(color: colored::Color) -> Color {
+    match color {
+        colored::Color::Red => Color::Red,
+        colored::Color::Yellow => Color::Yellow,
+        colored::Color::Green => Color::Green,
+        _ => Color::Reset,
+    }
+}
+
+fn popup_area(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{test_pool, TEST_PROJECT};
+    use ratatui::backend::TestBackend;
+
+    async fn dashboard<'a>(pool: &'a SqlitePool, config: &'a Config) -> Dashboard<'a> {
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack,
+                               resource_names)
+            VALUES (1, 1, 'Start', 0, '2026-10-19', '2026-10-19', 0, ''),
+                   (1, 2, 'Design', 5, '2026-10-19', '2026-10-23', 0, 'DEV'),
+                   (1, 3, 'Build', 3, '2026-10-26', '2026-10-28', 0, 'DEV');
+            INSERT INTO dependencies (project_id, task_id, predecessor_id)
+            VALUES (1, 2, 1),
+                   (1, 3, 2);
+            INSERT INTO team (id, name, role) VALUES (1, 'dana', 'DEV');
+            INSERT INTO task_data (project_id, task_id, member_id) VALUES (1, 2, 1);
+            "#
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        let mut dashboard = Dashboard::new(pool, TEST_PROJECT, config);
+        dashboard.refresh().await.unwrap();
+        dashboard
+    }
+
+    fn message(dashboard: &Dashboard) -> String {
+        dashboard
+            .message
+            .as_ref()
+            .map(|message| message.to_string())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn shows_the_selected_task_with_its_links() {
+        let pool = test_pool().await;
+        let config = Config::default();
+        let mut dashboard = dashboard(&pool, &config).await;
+        assert_eq!(
+            dashboard
+                .tasks
+                .iter()
+                .map(|task| task.id)
+                .collect::<Vec<i64>>(),
+            vec![2, 3]
+        );
+        assert_eq!(dashboard.selected().map(|task| task.id), Some(2));
+
+        let details: Vec<String> = dashboard.details().iter().map(Line::to_string).collect();
+        assert_eq!(details[0], "#2 Design");
+        assert_eq!(details[1], "Assignee: Dana   Planned for: DEV");
+        assert_eq!(details[3], "Predecessors: #1 Start");
+        assert_eq!(details[4], "Successors: #3 Build");
+
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| dashboard.draw(frame)).unwrap();
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("Tasks (Pending)"));
+        assert!(screen.contains("Build"));
+        assert!(screen.contains(HELP));
+    }
+
+    #[tokio::test]
+    async fn stays_on_the_selected_task_after_refreshing() {
+        let pool = test_pool().await;
+        let config = Config::default();
+        let mut dashboard = dashboard(&pool, &config).await;
+        dashboard.table.select_next();
+        dashboard.status = TaskStatus::Unassigned;
+        dashboard.refresh().await.unwrap();
+        assert_eq!(dashboard.selected().map(|task| task.id), Some(3));
+
+        dashboard.status = TaskStatus::Completed;
+        dashboard.refresh().await.unwrap();
+        assert!(dashboard.selected().is_none());
+        assert!(!dashboard.open(Popup::Complete));
+        assert_eq!(message(&dashboard), "No task selected");
+    }
+
+    #[tokio::test]
+    async fn completes_the_selected_task_once_confirmed() {
+        let pool = test_pool().await;
+        let config = Config::default();
+        let mut dashboard = dashboard(&pool, &config).await;
+
+        dashboard.open(Popup::Complete);
+        let popup = dashboard.popup.take().unwrap();
+        assert!(!dashboard
+            .handle_popup(popup, KeyCode::Char('n'))
+            .await
+            .unwrap());
+        assert!(dashboard.popup.is_none());
+
+        dashboard.open(Popup::Complete);
+        let popup = dashboard.popup.take().unwrap();
+        assert!(dashboard
+            .handle_popup(popup, KeyCode::Char('y'))
+            .await
+            .unwrap());
+        assert_eq!(message(&dashboard), "✅ Completed #2");
+        dashboard.refresh().await.unwrap();
+        assert_eq!(
+            dashboard
+                .tasks
+                .iter()
+                .map(|task| task.id)
+                .collect::<Vec<i64>>(),
+            vec![3]
+        );
+    }
+
+    #[tokio::test]
+    async fn assigns_the_chosen_member() {
+        let pool = test_pool().await;
+        let config = Config::default();
+        let mut dashboard = dashboard(&pool, &config).await;
+        dashboard.table.select_next();
+
+        dashboard.open(Popup::Assign(ListState::default()));
+        let popup = dashboard.popup.take().unwrap();
+        assert!(dashboard.handle_popup(popup, KeyCode::Enter).await.unwrap());
+        assert_eq!(message(&dashboard), "✅ Assigned #3 to dana");
+        dashboard.refresh().await.unwrap();
+        assert_eq!(
+            dashboard.selected().and_then(|task| task.assignee.clone()),
+            Some("dana".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn commands_report_their_errors() {
+        let pool = test_pool().await;
+        let config = Config::default();
+        let mut dashboard = dashboard(&pool, &config).await;
+        dashboard.table.select_next();
+
+        // Work is only logged on assigned tasks
+        dashboard.open(Popup::Log(ListState::default()));
+        let popup = dashboard.popup.take().unwrap();
+        assert!(dashboard.handle_popup(popup, KeyCode::Enter).await.unwrap());
+        assert_eq!(
+            message(&dashboard),
+            "Task #3 is not assigned or already completed"
+        );
+    }
+}
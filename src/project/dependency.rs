@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::anyhow;
+use serde::Serialize;
+use sqlx::{SqliteConnection, SqlitePool};
+
+// Lag units MS Project writes with the working days they stand for, the `e` variants are
+// elapsed time and counted like working time
+const LAG_UNITS: [(&str, f64); 27] = [
+    ("", 1.0),
+    ("d", 1.0),
+    ("day", 1.0),
+    ("days", 1.0),
+    ("ed", 1.0),
+    ("eday", 1.0),
+    ("edays", 1.0),
+    ("w", 5.0),
+    ("wk", 5.0),
+    ("wks", 5.0),
+    ("week", 5.0),
+    ("weeks", 5.0),
+    ("ew", 5.0),
+    ("ewk", 5.0),
+    ("ewks", 5.0),
+    ("h", 1.0 / 8.0),
+    ("hr", 1.0 / 8.0),
+    ("hrs", 1.0 / 8.0),
+    ("hour", 1.0 / 8.0),
+    ("hours", 1.0 / 8.0),
+    ("eh", 1.0 / 8.0),
+    ("m", 1.0 / 480.0),
+    ("min", 1.0 / 480.0),
+    ("mins", 1.0 / 480.0),
+    ("mo", 20.0),
+    ("mon", 20.0),
+    ("mons", 20.0),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub(crate) enum LinkType {
+    // The successor starts after the predecessor finished
+    #[serde(rename = "FS")]
+    FinishToStart,
+    // The successor starts once the predecessor started
+    #[serde(rename = "SS")]
+    StartToStart,
+    // The successor finishes once the predecessor finished
+    #[serde(rename = "FF")]
+    FinishToFinish,
+    // The successor finishes once the predecessor started
+    #[serde(rename = "SF")]
+    StartToFinish,
+}
+
+impl LinkType {
+    fn code(self) -> &'static str {
+        match self {
+            LinkType::FinishToStart => "FS",
+            LinkType::StartToStart => "SS",
+            LinkType::FinishToFinish => "FF",
+            LinkType::StartToFinish => "SF",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        match code.to_uppercase().as_str() {
+            "FS" => Some(LinkType::FinishToStart),
+            "SS" => Some(LinkType::StartToStart),
+            "FF" => Some(LinkType::FinishToFinish),
+            "SF" => Some(LinkType::StartToFinish),
+            _ => None,
+        }
+    }
+}
+
+// Dependency on a predecessor, the lag is in working days and negative for lead time
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub(crate) struct Link {
+    pub(crate) predecessor: i64,
+    #[serde(rename = "type")]
+    pub(crate) link_type: LinkType,
+    pub(crate) lag: i64,
+}
+
+impl Link {
+    pub(crate) fn new(predecessor: i64, link_type: LinkType, lag: i64) -> Self {
+        Link {
+            predecessor,
+            link_type,
+            lag,
+        }
+    }
+
+    // Link type and lag as written after the predecessor, empty for a plain finish to start
+    pub(crate) fn type_and_lag(&self) -> String {
+        match (self.link_type, self.lag) {
+            (LinkType::FinishToStart, 0) => String::new(),
+            (link_type, 0) => link_type.code().to_string(),
+            (link_type, lag) => format!(
+                "{}{:+} {}",
+                link_type.code(),
+                lag,
+                if lag.abs() == 1 { "day" } else { "days" }
+            ),
+        }
+    }
+}
+
+// In the notation of the MS Project CSV export, e.g. "3", "4SS" or "5FF+2 days"
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.predecessor, self.type_and_lag())
+    }
+}
+
+// A link as MS Project writes them, e.g. "3", "5SS", "4FS+2 days" or "7FF-1d"
+pub(crate) fn parse_link(link: &str) -> Result<Link, String> {
+    let link = link.trim();
+    let digits: String = link.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let predecessor = digits
+        .parse::<i64>()
+        .map_err(|_| "it does not start with a task ID".to_string())?;
+
+    let rest = link[digits.len()..].trim();
+    let (link_type, lag) = rest.split_at(rest.find(['+', '-']).unwrap_or(rest.len()));
+    let link_type = match link_type.trim() {
+        "" => LinkType::FinishToStart,
+        code => LinkType::from_code(code)
+            .ok_or_else(|| format!("{} is not a link type (FS, SS, FF or SF)", code))?,
+    };
+
+    let lag = match lag.split_at_checked(1) {
+        Some((sign, amount_and_unit)) => {
+            let amount_and_unit = amount_and_unit.trim();
+            let amount: String = amount_and_unit
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == '.')
+                .collect();
+            let unit = amount_and_unit[amount.len()..].trim().to_lowercase();
+            let amount = amount
+                .parse::<f64>()
+                .map_err(|_| format!("the lag {} is not a number", amount_and_unit))?;
+            let (_, days) = LAG_UNITS
+                .iter()
+                .find(|(name, _)| *name == unit)
+                .ok_or_else(|| format!("{} is not a unit for the lag", unit))?;
+            let lag = (amount * days).round() as i64;
+            if sign == "-" {
+                -lag
+            } else {
+                lag
+            }
+        }
+        None => 0,
+    };
+    Ok(Link::new(predecessor, link_type, lag))
+}
+
+// All links of a task, ordered by predecessor
+pub(crate) fn parse_links(links: &str) -> Result<Vec<Link>, String> {
+    let mut links = links
+        .split(',')
+        .filter(|link| !link.trim().is_empty())
+        .map(|link| parse_link(link).map_err(|reason| format!("\"{}\": {}", link.trim(), reason)))
+        .collect::<Result<Vec<Link>, String>>()?;
+    links.sort_by_key(|link| link.predecessor);
+    Ok(links)
+}
+
+pub(crate) fn format_links(links: &[Link]) -> String {
+    links
+        .iter()
+        .map(Link::to_string)
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// Links of every task that has predecessors, keyed on the task
+pub(crate) async fn load(
+    pool: &SqlitePool,
+    project_id: i64,
+) -> anyhow::Result<HashMap<i64, Vec<Link>>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT task_id, predecessor_id, link_type, lag
+        FROM dependencies
+        WHERE project_id = ?1
+        ORDER BY task_id, predecessor_id
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut links: HashMap<i64, Vec<Link>> = HashMap::new();
+    for row in rows {
+        let link_type = LinkType::from_code(&row.link_type).ok_or_else(|| {
+            anyhow!(
+                "Unknown link type {} of task #{}",
+                row.link_type,
+                row.task_id
+            )
+        })?;
+        links.entry(row.task_id).or_default().push(Link::new(
+            row.predecessor_id,
+            link_type,
+            row.lag,
+        ));
+    }
+    Ok(links)
+}
+
+// Replace the predecessors of a task
+pub(crate) async fn save(
+    conn: &mut SqliteConnection,
+    project_id: i64,
+    task_id: i64,
+    links: &[Link],
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"DELETE FROM dependencies WHERE project_id = ?1 AND task_id = ?2"#,
+        project_id,
+        task_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    for link in links {
+        let link_type = link.link_type.code();
+        sqlx::query!(
+            r#"
+            INSERT INTO dependencies (project_id, task_id, predecessor_id, link_type, lag)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+            project_id,
+            task_id,
+            link.predecessor,
+            link_type,
+            link.lag
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+// Drop all links from and to a task before it is deleted
+pub(crate) async fn remove_task(
+    conn: &mut SqliteConnection,
+    project_id: i64,
+    task_id: i64,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+        DELETE FROM dependencies
+        WHERE project_id = ?1
+          AND (task_id = ?2 OR predecessor_id = ?2)
+        "#,
+        project_id,
+        task_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_predecessor_as_finish_to_start() {
+        assert_eq!(
+            parse_link(" 3 "),
+            Ok(Link::new(3, LinkType::FinishToStart, 0))
+        );
+    }
+
+    #[test]
+    fn parses_link_types_without_case() {
+        assert_eq!(
+            parse_link("5SS"),
+            Ok(Link::new(5, LinkType::StartToStart, 0))
+        );
+        assert_eq!(
+            parse_link("5ff"),
+            Ok(Link::new(5, LinkType::FinishToFinish, 0))
+        );
+        assert_eq!(
+            parse_link("5 SF"),
+            Ok(Link::new(5, LinkType::StartToFinish, 0))
+        );
+        assert!(parse_link("5XX").is_err());
+        assert!(parse_link("SS").is_err());
+    }
+
+    #[test]
+    fn converts_lag_units_to_working_days() {
+        let lag = |link: &str| parse_link(link).map(|link| link.lag);
+        assert_eq!(lag("4FS+2 days"), Ok(2));
+        assert_eq!(lag("4FS+2d"), Ok(2));
+        assert_eq!(lag("4FS+1 wk"), Ok(5));
+        assert_eq!(lag("4FS+1w"), Ok(5));
+        assert_eq!(lag("4FS+16h"), Ok(2));
+        assert_eq!(lag("4FS+960 mins"), Ok(2));
+        assert_eq!(lag("4FS+1mo"), Ok(20));
+        assert_eq!(lag("4FS+3 edays"), Ok(3));
+        assert_eq!(lag("4FS+1.5d"), Ok(2));
+        assert!(lag("4FS+2 fortnights").is_err());
+        assert!(lag("4FS+days").is_err());
+    }
+
+    #[test]
+    fn negative_lag_is_lead_time() {
+        assert_eq!(
+            parse_link("7FF-1d"),
+            Ok(Link::new(7, LinkType::FinishToFinish, -1))
+        );
+        assert_eq!(
+            parse_link("7-2 days"),
+            Ok(Link::new(7, LinkType::FinishToStart, -2))
+        );
+    }
+
+    #[test]
+    fn parses_links_ordered_by_predecessor() {
+        assert_eq!(
+            parse_links("5SS, 3,,4FS+2 days"),
+            Ok(vec![
+                Link::new(3, LinkType::FinishToStart, 0),
+                Link::new(4, LinkType::FinishToStart, 2),
+                Link::new(5, LinkType::StartToStart, 0),
+            ])
+        );
+        assert_eq!(parse_links(""), Ok(vec![]));
+        assert!(parse_links("3,x").unwrap_err().contains("\"x\""));
+    }
+
+    #[test]
+    fn formats_links_like_ms_project_and_reads_them_back() {
+        let links = vec![
+            Link::new(3, LinkType::FinishToStart, 0),
+            Link::new(4, LinkType::StartToStart, 0),
+            Link::new(5, LinkType::FinishToFinish, 2),
+            Link::new(6, LinkType::FinishToStart, -1),
+        ];
+        let formatted = format_links(&links);
+        assert_eq!(formatted, "3,4SS,5FF+2 days,6FS-1 day");
+        assert_eq!(parse_links(&formatted), Ok(links));
+    }
+}
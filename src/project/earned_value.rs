@@ -1,77 +1,125 @@
-use std::fs;
-use std::path::PathBuf;
+use std::fmt;
 
+use anyhow::bail;
 use chrono::{Datelike, Duration, Local, NaiveDate};
-use inquire::error::InquireResult;
-use inquire::{Confirm, Select};
-use plotly::color::NamedColor::{Blue, Green, Orange, Red};
-use plotly::common::{Mode, Title};
+use clap::ValueEnum;
+use colored::Colorize;
+use plotly::color::NamedColor::{Blue, CadetBlue, Green, MediumPurple, Red, SlateBlue, SteelBlue};
+use plotly::common::{DashType, Mode, Title};
 use plotly::layout::Axis;
-use plotly::{common, ImageFormat, Layout, Plot, Scatter};
-use promptly::prompt_default;
+use plotly::{common, Layout, Plot, Scatter};
+use prettytable::{row, Table};
+use serde::Serialize;
 use sqlx::{Pool, Sqlite};
 
-pub(crate) async fn generate_chart(pool: &Pool<Sqlite>, chart_title: &str) -> anyhow::Result<()> {
+use crate::config::Config;
+use crate::project::chart::{
+    export_chart, generate_week_numbers, week_labels, week_number, ChartOutput,
+};
+use crate::project::dfmt;
+
+pub(crate) async fn generate_chart(
+    pool: &Pool<Sqlite>,
+    project_id: i64,
+    config: &Config,
+    chart_title: &str,
+    baselines: &[String],
+    method: EvMethod,
+    output: &ChartOutput,
+) -> anyhow::Result<()> {
     // Insert the task, then obtain the ID of this row
     let dates = sqlx::query!(
         r#"
-        SELECT min(start_date) as start_date, max(finish_date) as end_date FROM tasks
+        SELECT min(start_date) as start_date, max(finish_date) as end_date
+        FROM tasks
+        WHERE project_id = ?1
         "#,
+        project_id
     )
     .fetch_one(pool)
     .await?;
 
-    let start_date = dates.start_date.expect("No start date");
-    let end_date = dates.end_date.expect("No end date");
+    let (Some(start_date), Some(end_date)) = (dates.start_date, dates.end_date) else {
+        bail!("No tasks to chart, import a plan first");
+    };
 
     // Generate weeks between start and end date
     let from_date = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
         .expect("Error parsing start date")
         - Duration::weeks(1);
+    let planned_finish =
+        NaiveDate::parse_from_str(&end_date, "%Y-%m-%d").expect("Error parsing end date");
+
+    // Baselines to draw the planned progress for, next to the current plan
+    let mut baseline_tasks = vec![];
+    for name in baselines {
+        let tasks = sqlx::query!(
+            r#"SELECT bt.duration as duration,
+               bt.finish_date as finish_date
+               FROM baseline_tasks bt
+                 JOIN baselines b ON b.id = bt.baseline_id
+               WHERE b.project_id = ?1 AND b.name = ?2 AND bt.duration > 0
+        "#,
+            project_id,
+            name
+        )
+        .fetch_all(pool)
+        .await?;
+        if tasks.is_empty() {
+            bail!("No baseline named {}", name);
+        }
+        baseline_tasks.push((name, tasks));
+    }
+    let baseline_finish = baseline_tasks
+        .iter()
+        .flat_map(|(_, tasks)| tasks.iter())
+        .filter_map(|task| NaiveDate::parse_from_str(&task.finish_date, "%Y-%m-%d").ok())
+        .max()
+        .unwrap_or(planned_finish);
+
+    // Keep the current week on the chart when the project runs late
     let week_numbers = generate_week_numbers(
         from_date,
-        NaiveDate::parse_from_str(&end_date, "%Y-%m-%d").expect("Error parsing end date"),
+        planned_finish
+            .max(baseline_finish)
+            .max(Local::now().date_naive()),
     );
 
     let total_effort_result = sqlx::query!(
         r#"
-        SELECT sum(duration) as total_effort FROM tasks
+        SELECT sum(duration) as total_effort FROM tasks WHERE project_id = ?1
         "#,
+        project_id
     )
     .fetch_one(pool)
     .await?;
 
-    let total_effort = total_effort_result.total_effort.expect("No total effort");
+    let total_effort = match total_effort_result.total_effort {
+        Some(total_effort) if total_effort > 0 => total_effort,
+        _ => bail!("No work planned, nothing to chart"),
+    };
 
     let tasks = sqlx::query!(
-        r#"SELECT duration,
-           CAST(CASE
-               WHEN strftime('%Y%W', finish_date) < 10 THEN '0' || strftime('%W', finish_date)
-               ELSE strftime('%Y%W', finish_date)
-               END AS INTEGER) AS should_finish,
-           CAST(CASE
-               WHEN strftime('%Y%W', finished_at) < 10 THEN '0' || strftime('%W', finished_at)
-               ELSE strftime('%Y%W', finished_at)
-               END AS INTEGER) AS actual_finish
+        r#"SELECT duration, finish_date
            FROM tasks
-             LEFT JOIN task_data td on tasks.id = td.task_id
-           WHERE duration > 0
+             LEFT JOIN task_data td on td.project_id = tasks.project_id AND td.task_id = tasks.id
+           WHERE tasks.project_id = ?1
+             AND duration > 0
            ORDER BY start_date, total_slack DESC;
     "#,
+        project_id
     )
     .fetch_all(pool)
     .await?;
 
     let work_effort = sqlx::query!(
-        r#"SELECT sum(duration) as effort,
-        CAST(CASE
-                WHEN strftime('%Y%W', date) < 10 THEN '0' || strftime('%W', date)
-                ELSE strftime('%Y%W', date)
-           END AS INTEGER) AS week
+        r#"SELECT sum(duration) as "effort!: f64", date as "date!"
         FROM timesheet
-        GROUP BY week
-        ORDER BY week ASC;
+        WHERE project_id = ?1
+        GROUP BY date
+        ORDER BY date ASC;
     "#,
+        project_id
     )
     .fetch_all(pool)
     .await?;
@@ -80,31 +128,24 @@ pub(crate) async fn generate_chart(pool: &Pool<Sqlite>, chart_title: &str) -> an
     let mut earned_value = vec![0.0f32; week_numbers.len()];
     let mut effort = vec![0.0f32; week_numbers.len()];
 
+    // Every curve is grouped by the same weeks, so they line up on the chart
+    let parse =
+        |date: &str| NaiveDate::parse_from_str(date.get(..10).unwrap_or(date), "%Y-%m-%d").ok();
+    let week_index = |date: NaiveDate| week_numbers.iter().position(|&x| x == week_number(date));
+
     for task in tasks {
         let task_value = (task.duration as f32 / total_effort as f32) * 100f32;
 
-        let value_planned = task_value;
-
-        let value_actual = match task.actual_finish {
-            None => 0.0,
-            Some(_) => task_value,
-        };
-
         // Planned value
-        if let Some(index) = week_numbers
-            .iter()
-            .position(|&x| x == task.should_finish.unwrap())
-        {
-            planned_value[index] += value_planned;
+        if let Some(index) = parse(&task.finish_date).and_then(week_index) {
+            planned_value[index] += task_value;
         }
+    }
 
-        // Earned value
-        if task.actual_finish.is_some() {
-            let index = week_numbers
-                .iter()
-                .position(|&x| x == task.actual_finish.unwrap() as i32)
-                .unwrap();
-            earned_value[index] += value_actual;
+    // Earned value
+    for (date, days) in earned_days(pool, project_id, method).await? {
+        if let Some(index) = week_index(date) {
+            earned_value[index] += (days / total_effort as f64 * 100.0) as f32;
         }
     }
 
@@ -113,13 +154,25 @@ pub(crate) async fn generate_chart(pool: &Pool<Sqlite>, chart_title: &str) -> an
         planned_value[i] += planned_value[i - 1];
     }
 
-    // aggregate planned_value
-    let date = Local::now();
-    let year = date.year();
-    let week = date.iso_week().week();
-    let current_week = (year.to_string() + format!("{:02}", week).as_str())
-        .parse()
-        .unwrap();
+    // Planned value of each baseline, relative to the effort in that baseline
+    let baseline_values: Vec<(&String, Vec<f32>)> = baseline_tasks
+        .iter()
+        .map(|(name, tasks)| {
+            let baseline_effort: i64 = tasks.iter().map(|task| task.duration).sum();
+            let mut values = vec![0.0f32; week_numbers.len()];
+            for task in tasks {
+                if let Some(index) = parse(&task.finish_date).and_then(week_index) {
+                    values[index] += (task.duration as f32 / baseline_effort as f32) * 100f32;
+                }
+            }
+            for i in 1..values.len() {
+                values[i] += values[i - 1];
+            }
+            (*name, values)
+        })
+        .collect();
+
+    let current_week = week_number(Local::now().date_naive());
 
     for i in 1..earned_value.len() {
         if week_numbers[i] > current_week {
@@ -139,10 +192,9 @@ pub(crate) async fn generate_chart(pool: &Pool<Sqlite>, chart_title: &str) -> an
 
     // Effort
     for work_line in work_effort {
-        let week = work_line.week.unwrap() as i32;
-        let eff = work_line.effort.unwrap() as f32;
-        let index = week_numbers.iter().position(|&x| x == week).unwrap();
-        effort[index] += (eff / total_effort as f32) * 100.0;
+        if let Some(index) = parse(&work_line.date).and_then(week_index) {
+            effort[index] += (work_line.effort as f32 / total_effort as f32) * 100.0;
+        }
     }
 
     // aggregate effort
@@ -158,11 +210,38 @@ pub(crate) async fn generate_chart(pool: &Pool<Sqlite>, chart_title: &str) -> an
     effort = effort.into_iter().rev().skip_while(|&x| x == 0.0).collect();
     effort.reverse();
 
-    let week_prefix = "W";
-    let x_axis: Vec<String> = week_numbers
+    // Forecast completion from the earned value trend
+    let forecasts = forecast_completion(&earned_value);
+    for forecast in &forecasts {
+        let completion_date =
+            from_date + Duration::days((forecast.completion * 7.0).round() as i64);
+        let slip = (completion_date - planned_finish).num_days() as f64 / 7.0;
+        println!(
+            "🔮 {} forecast: 100% on {} ({:.1} weeks {} than the planned finish {})",
+            forecast.name,
+            dfmt(completion_date, &config.dates),
+            slip.abs(),
+            if slip > 0.0 { "later" } else { "earlier" },
+            dfmt(planned_finish, &config.dates)
+        );
+    }
+    if forecasts.is_empty() {
+        println!("🔮 Not enough earned value yet to forecast the completion date");
+    }
+
+    // Extend the weeks so the forecasts can reach 100%
+    let last_week = forecasts
         .iter()
-        .map(|&num| week_prefix.to_owned() + &num.to_string().get(2..).unwrap_or_default())
-        .collect();
+        .map(|forecast| forecast.completion.ceil() as usize)
+        .max()
+        .unwrap_or(0);
+    let week_numbers = if last_week >= week_numbers.len() {
+        generate_week_numbers(from_date, from_date + Duration::weeks(last_week as i64))
+    } else {
+        week_numbers
+    };
+
+    let x_axis = week_labels(&week_numbers);
 
     let trace1 = Scatter::new(x_axis.clone(), effort)
         .mode(Mode::Lines)
@@ -176,7 +255,7 @@ pub(crate) async fn generate_chart(pool: &Pool<Sqlite>, chart_title: &str) -> an
     let trace3 = Scatter::new(x_axis.clone(), earned_value)
         .mode(Mode::Lines)
         .line(common::Line::new().color(Green))
-        .name("Earned value 💶");
+        .name(format!("Earned value ({}) 💶", method).as_str());
 
     let layout = Layout::new()
         .title(Title::new(chart_title))
@@ -187,77 +266,599 @@ pub(crate) async fn generate_chart(pool: &Pool<Sqlite>, chart_title: &str) -> an
     plot.add_trace(trace1);
     plot.add_trace(trace2);
     plot.add_trace(trace3);
+    let baseline_colors = [SteelBlue, SlateBlue, CadetBlue, MediumPurple];
+    for (i, (name, values)) in baseline_values.into_iter().enumerate() {
+        let trace = Scatter::new(x_axis.clone(), values)
+            .mode(Mode::Lines)
+            .line(
+                common::Line::new()
+                    .color(baseline_colors[i % baseline_colors.len()])
+                    .dash(DashType::DashDot),
+            )
+            .name(format!("Planned Progress ({}) 📸", name).as_str());
+        plot.add_trace(trace);
+    }
+    for forecast in forecasts {
+        let weeks = forecast.first_week..=forecast.completion.ceil() as usize;
+        let values: Vec<f64> = weeks
+            .clone()
+            .map(|week| (forecast.curve)(week as f64).min(100.0))
+            .collect();
+        let trace = Scatter::new(x_axis[weeks].to_vec(), values)
+            .mode(Mode::Lines)
+            .line(common::Line::new().color(Green).dash(forecast.dash))
+            .name(format!("{} forecast 🔮", forecast.name).as_str());
+        plot.add_trace(trace);
+    }
     plot.set_layout(layout);
 
-    let options = vec![ImageFormat::PDF, ImageFormat::SVG, ImageFormat::PNG];
-    let ans = Select::new("Output file format?", options).prompt();
+    export_chart(&plot, output, "ev_chart", "Earned Value Chart")
+}
 
-    let image_format = if ans.is_err() {
-        println!("Could not get output file format, defaulting to PDF");
-        ImageFormat::PDF
-    } else {
-        match ans.unwrap() {
-            ImageFormat::PNG => ImageFormat::PNG,
-            ImageFormat::SVG => ImageFormat::SVG,
-            ImageFormat::PDF => ImageFormat::PDF,
-            _ => {
-                println!("Unsupported file format, defaulting to PDF");
-                ImageFormat::PDF
+// How much of its value a task has earned before it is completed
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub(crate) enum EvMethod {
+    /// Nothing until the task is completed
+    #[default]
+    #[value(name = "0-100")]
+    ZeroHundred,
+    /// Half when work starts, the rest when the task is completed
+    #[value(name = "50-50")]
+    FiftyFifty,
+    /// The percent complete recorded with the progress command
+    #[value(name = "percent")]
+    PercentComplete,
+}
+
+impl fmt::Display for EvMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvMethod::ZeroHundred => write!(f, "0/100"),
+            EvMethod::FiftyFifty => write!(f, "50/50"),
+            EvMethod::PercentComplete => write!(f, "% complete"),
+        }
+    }
+}
+
+// Days of planned work earned at each date, following the earned value method
+async fn earned_days(
+    pool: &Pool<Sqlite>,
+    project_id: i64,
+    method: EvMethod,
+) -> anyhow::Result<Vec<(NaiveDate, f64)>> {
+    let tasks = sqlx::query!(
+        r#"
+        SELECT t.id           as "id!",
+               t.duration     as duration,
+               td.finished_at as finished_at,
+               (SELECT min(ts.date)
+                FROM timesheet ts
+                WHERE ts.project_id = t.project_id
+                  AND ts.task_id = t.id) as "first_logged: String",
+               (SELECT min(tp.date)
+                FROM task_progress tp
+                WHERE tp.project_id = t.project_id
+                  AND tp.task_id = t.id
+                  AND tp.percent_complete > 0) as "first_progress: String"
+        FROM tasks t
+                 LEFT JOIN task_data td ON td.project_id = t.project_id AND td.task_id = t.id
+        WHERE t.project_id = ?1
+          AND t.duration > 0
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let progress = sqlx::query!(
+        r#"
+        SELECT task_id          as "task_id!",
+               date             as "date!",
+               percent_complete as "percent_complete!"
+        FROM task_progress
+        WHERE project_id = ?1
+        ORDER BY task_id, date, id
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let parse = |date: &str| NaiveDate::parse_from_str(date.get(..10).unwrap_or(date), "%Y-%m-%d");
+
+    let mut earned = vec![];
+    for task in tasks {
+        let duration = task.duration as f64;
+        let finished = task.finished_at.as_deref().map(parse).transpose()?;
+        match method {
+            EvMethod::ZeroHundred => {
+                if let Some(finished) = finished {
+                    earned.push((finished, duration));
+                }
+            }
+            EvMethod::FiftyFifty => {
+                let started = [task.first_logged, task.first_progress]
+                    .iter()
+                    .flatten()
+                    .map(|date| parse(date))
+                    .collect::<Result<Vec<NaiveDate>, _>>()?
+                    .into_iter()
+                    .chain(finished)
+                    .min();
+                if let Some(started) = started {
+                    earned.push((started, duration / 2.0));
+                }
+                if let Some(finished) = finished {
+                    earned.push((finished, duration / 2.0));
+                }
+            }
+            EvMethod::PercentComplete => {
+                let mut percent = 0;
+                for update in progress.iter().filter(|update| update.task_id == task.id) {
+                    earned.push((
+                        parse(&update.date)?,
+                        duration * (update.percent_complete - percent) as f64 / 100.0,
+                    ));
+                    percent = update.percent_complete;
+                }
+                if let Some(finished) = finished {
+                    earned.push((finished, duration * (100 - percent) as f64 / 100.0));
+                }
             }
         }
+    }
+    Ok(earned)
+}
+
+// Give up on forecasts that land more than ten years out
+const MAX_FORECAST_WEEKS: f64 = 520.0;
+
+// Trend line fitted to the earned value so far, extrapolated to 100%
+struct Forecast {
+    name: &'static str,
+    dash: DashType,
+    first_week: usize,
+    // Week index, counted from the first week on the chart, where the forecast reaches 100%
+    completion: f64,
+    curve: Box<dyn Fn(f64) -> f64>,
+}
+
+fn forecast_completion(earned_value: &[f32]) -> Vec<Forecast> {
+    // Fit from the week before earned value started to show up
+    let Some(first_week) = earned_value.iter().position(|&value| value > 0.0) else {
+        return vec![];
     };
+    let first_week = first_week.saturating_sub(1);
+    let points: Vec<(f64, f64)> = earned_value
+        .iter()
+        .enumerate()
+        .skip(first_week)
+        .map(|(week, &value)| (week as f64, value as f64))
+        .collect();
 
-    // Generate outfile
-    let today = Local::now();
-    let prefixed_file_name = format!(
-        "charts/ev_chart-week-{}-({}).{image_format}",
-        today.iso_week().week(),
-        today.format("%s").to_string()
-    );
+    let mut forecasts = vec![];
 
-    let path = PathBuf::from(prefixed_file_name);
-    let out_file: PathBuf = prompt_default("Enter path to generated chart:", path)?;
-    fs::create_dir_all(&out_file.parent().unwrap().to_path_buf())?;
+    if let Some((intercept, slope)) = least_squares(&points).filter(|(_, slope)| *slope > 0.0) {
+        forecasts.push(Forecast {
+            name: "Linear",
+            dash: DashType::Dash,
+            first_week,
+            completion: (100.0 - intercept) / slope,
+            curve: Box::new(move |week| intercept + slope * week),
+        });
+    }
 
-    match image_format {
-        ImageFormat::PNG => plot.write_image(out_file.clone(), ImageFormat::PNG, 1800, 1000, 1.0),
-        ImageFormat::SVG => plot.write_image(out_file.clone(), ImageFormat::SVG, 1800, 1000, 1.0),
-        ImageFormat::PDF => plot.write_image(out_file.clone(), ImageFormat::PDF, 1800, 1000, 1.0),
-        _ => plot.write_image(out_file.clone(), ImageFormat::PDF, 1800, 1000, 1.0),
-    };
+    // The shallow S is a logistic curve, linear in log(y / (100 - y)). It never quite reaches
+    // 100%, so the project counts as done at 99%.
+    let logits: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|(_, value)| *value > 0.0 && *value < 100.0)
+        .map(|(week, value)| (*week, (value / (100.0 - value)).ln()))
+        .collect();
+    if let Some((intercept, slope)) = least_squares(&logits).filter(|(_, slope)| *slope > 0.0) {
+        let completion = (99.0f64.ln() - intercept) / slope;
+        forecasts.push(Forecast {
+            name: "Shallow S",
+            dash: DashType::Dot,
+            first_week,
+            completion,
+            curve: Box::new(move |week| {
+                if week >= completion.ceil() {
+                    100.0
+                } else {
+                    100.0 / (1.0 + (-(intercept + slope * week)).exp())
+                }
+            }),
+        });
+    }
 
-    let ans = Confirm::new("Open the generated Earned Value Chart?")
-        .with_default(false)
-        .prompt();
+    forecasts
+        .into_iter()
+        .filter(|forecast| {
+            forecast.completion.is_finite() && forecast.completion < MAX_FORECAST_WEEKS
+        })
+        .collect()
+}
 
-    let file = out_file.clone();
+// Ordinary least squares, returning intercept and slope
+fn least_squares(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if variance == 0.0 {
+        return None;
+    }
+    let slope = covariance / variance;
+    Some((mean_y - slope * mean_x, slope))
+}
 
-    if ans.is_ok() && ans.unwrap() == true {
-        opener::open(file).expect("Could not open file");
-    } else {
-        println!(
-            "Ok, the chart is saved in the charts folder: {}",
-            file.to_str().unwrap()
-        );
+#[derive(Debug, Serialize)]
+pub(crate) struct EarnedValueMetrics {
+    status_date: NaiveDate,
+    budget_at_completion: f64,
+    planned_value: f64,
+    earned_value: f64,
+    actual_cost: f64,
+    schedule_variance: f64,
+    cost_variance: f64,
+    schedule_performance_index: Option<f64>,
+    cost_performance_index: Option<f64>,
+    estimate_at_completion: Option<f64>,
+    estimate_to_complete: Option<f64>,
+    variance_at_completion: Option<f64>,
+    to_complete_performance_index: Option<f64>,
+}
+
+// Earned value metrics in person-days at the status date, defaults to the end of the current week
+pub(crate) async fn report(
+    pool: &Pool<Sqlite>,
+    project_id: i64,
+    config: &Config,
+    status_date: Option<NaiveDate>,
+    method: EvMethod,
+    json: bool,
+) -> anyhow::Result<()> {
+    let status_date = status_date.unwrap_or_else(|| {
+        let today = Local::now().date_naive();
+        today + Duration::days(6 - today.weekday().num_days_from_monday() as i64)
+    });
+    let metrics = calculate_metrics(pool, project_id, status_date, method).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&metrics)?);
+        return Ok(());
     }
 
-    // To open in browser
-    // plot.show()
+    let mut table = Table::new();
+    table.add_row(row!["Metric".bold(), "Value".bold(), "% of budget".bold()]);
+    let percent = |value: f64| format!("{:.1} %", value / metrics.budget_at_completion * 100.0);
+    let days = |value: f64| format!("{:.1} days", value);
+    let index = |value: Option<f64>| match value {
+        Some(value) if value < 1.0 => format!("{:.2}", value).red(),
+        Some(value) => format!("{:.2}", value).green(),
+        None => "--".normal(),
+    };
+    let optional_days = |value: Option<f64>| value.map(days).unwrap_or("--".to_string());
+    let optional_percent = |value: Option<f64>| value.map(percent).unwrap_or("--".to_string());
+
+    table.add_row(row![
+        "Budget at completion (BAC)",
+        days(metrics.budget_at_completion),
+        percent(metrics.budget_at_completion)
+    ]);
+    table.add_row(row![
+        "Planned value (PV)",
+        days(metrics.planned_value),
+        percent(metrics.planned_value)
+    ]);
+    table.add_row(row![
+        "Earned value (EV)",
+        days(metrics.earned_value),
+        percent(metrics.earned_value)
+    ]);
+    table.add_row(row![
+        "Actual cost (AC)",
+        days(metrics.actual_cost),
+        percent(metrics.actual_cost)
+    ]);
+    table.add_row(row![
+        "Schedule variance (SV)",
+        days(metrics.schedule_variance),
+        percent(metrics.schedule_variance)
+    ]);
+    table.add_row(row![
+        "Cost variance (CV)",
+        days(metrics.cost_variance),
+        percent(metrics.cost_variance)
+    ]);
+    table.add_row(row![
+        "Schedule performance index (SPI)",
+        index(metrics.schedule_performance_index),
+        ""
+    ]);
+    table.add_row(row![
+        "Cost performance index (CPI)",
+        index(metrics.cost_performance_index),
+        ""
+    ]);
+    table.add_row(row![
+        "Estimate at completion (EAC)",
+        optional_days(metrics.estimate_at_completion),
+        optional_percent(metrics.estimate_at_completion)
+    ]);
+    table.add_row(row![
+        "Estimate to complete (ETC)",
+        optional_days(metrics.estimate_to_complete),
+        optional_percent(metrics.estimate_to_complete)
+    ]);
+    table.add_row(row![
+        "Variance at completion (VAC)",
+        optional_days(metrics.variance_at_completion),
+        optional_percent(metrics.variance_at_completion)
+    ]);
+    table.add_row(row![
+        "To-complete performance index (TCPI)",
+        match metrics.to_complete_performance_index {
+            Some(value) if value > 1.0 => format!("{:.2}", value).red(),
+            Some(value) => format!("{:.2}", value).green(),
+            None => "--".normal(),
+        },
+        ""
+    ]);
+
+    println!(
+        "📊 Earned value ({}) at {}",
+        method,
+        dfmt(metrics.status_date, &config.dates)
+    );
+    table.printstd();
     Ok(())
 }
 
-fn generate_week_numbers(start_date: NaiveDate, finish_date: NaiveDate) -> Vec<i32> {
-    let mut week_numbers = Vec::new();
-    let mut current_date = start_date;
+pub(crate) async fn calculate_metrics(
+    pool: &Pool<Sqlite>,
+    project_id: i64,
+    status_date: NaiveDate,
+    method: EvMethod,
+) -> anyhow::Result<EarnedValueMetrics> {
+    let date = status_date.format("%Y-%m-%d").to_string();
+    let values = sqlx::query!(
+        r#"
+        SELECT (SELECT sum(duration) FROM tasks WHERE project_id = ?2) as "budget: i64",
+               (SELECT sum(duration)
+                FROM tasks
+                WHERE project_id = ?2
+                  AND finish_date <= ?1) as "planned: i64",
+               (SELECT sum(duration)
+                FROM timesheet
+                WHERE project_id = ?2
+                  AND date <= ?1) as "actual: i64"
+        "#,
+        date,
+        project_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let budget = match values.budget {
+        Some(budget) if budget > 0 => budget as f64,
+        _ => bail!("No tasks to report on, import a plan first"),
+    };
+    let planned = values.planned.unwrap_or(0) as f64;
+    let earned: f64 = earned_days(pool, project_id, method)
+        .await?
+        .iter()
+        .filter(|(date, _)| *date <= status_date)
+        .map(|(_, days)| days)
+        .sum();
+    let actual = values.actual.unwrap_or(0) as f64;
+
+    let ratio = |numerator: f64, denominator: f64| {
+        if denominator == 0.0 {
+            None
+        } else {
+            Some(numerator / denominator)
+        }
+    };
+    let cost_performance_index = ratio(earned, actual);
+    let estimate_at_completion = cost_performance_index.and_then(|cpi| ratio(budget, cpi));
+
+    Ok(EarnedValueMetrics {
+        status_date,
+        budget_at_completion: budget,
+        planned_value: planned,
+        earned_value: earned,
+        actual_cost: actual,
+        schedule_variance: earned - planned,
+        cost_variance: earned - actual,
+        schedule_performance_index: ratio(earned, planned),
+        cost_performance_index,
+        estimate_at_completion,
+        estimate_to_complete: estimate_at_completion.map(|eac| eac - actual),
+        variance_at_completion: estimate_at_completion.map(|eac| budget - eac),
+        to_complete_performance_index: ratio(budget - earned, budget - actual),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{test_pool, TEST_PROJECT};
+
+    #[test]
+    fn least_squares_fits_a_line() {
+        assert_eq!(
+            least_squares(&[(0.0, 1.0), (1.0, 3.0), (2.0, 5.0)]),
+            Some((1.0, 2.0))
+        );
+        assert_eq!(least_squares(&[(1.0, 3.0)]), None);
+        assert_eq!(least_squares(&[(1.0, 3.0), (1.0, 5.0)]), None);
+    }
+
+    #[test]
+    fn forecasts_from_the_week_before_progress_shows_up() {
+        let forecasts = forecast_completion(&[0.0, 0.0, 10.0, 20.0, 30.0]);
+        let names: Vec<&str> = forecasts.iter().map(|forecast| forecast.name).collect();
+        assert_eq!(names, vec!["Linear", "Shallow S"]);
+
+        let linear = &forecasts[0];
+        assert_eq!(linear.first_week, 1);
+        assert!((linear.completion - 11.0).abs() < 1e-9);
+        assert!(((linear.curve)(4.0) - 30.0).abs() < 1e-9);
+
+        let shallow_s = &forecasts[1];
+        assert!(shallow_s.completion > linear.completion);
+        assert!((shallow_s.curve)(3.0) < 25.0);
+        assert_eq!((shallow_s.curve)(shallow_s.completion.ceil()), 100.0);
+    }
+
+    #[test]
+    fn no_forecast_without_progress() {
+        assert!(forecast_completion(&[]).is_empty());
+        assert!(forecast_completion(&[0.0, 0.0, 0.0]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn compares_earned_work_with_plan_and_cost() {
+        let pool = test_pool().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack)
+            VALUES (1, 1, 'Design', 5, '2026-10-19', '2026-10-23', 0),
+                   (1, 2, 'Build', 5, '2026-10-26', '2026-10-30', 0),
+                   (1, 3, 'Test', 10, '2026-11-02', '2026-11-13', 0);
+            INSERT INTO task_data (project_id, task_id, finished_at)
+            VALUES (1, 1, '2026-10-22 17:00:00');
+            INSERT INTO timesheet (project_id, task_id, date, duration)
+            VALUES (1, 1, '2026-10-22', 6),
+                   (1, 2, '2026-10-23', 2),
+                   (1, 2, '2026-10-26', 3);
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
 
-    while current_date <= finish_date {
-        let year = current_date.year(); // Get the last two digits of the year
-        let week_number = current_date.iso_week().week();
-        let compound_week = format!("{}{:02}", year, week_number)
-            .parse::<i32>()
+        let date = NaiveDate::from_ymd_opt(2026, 10, 23).unwrap();
+        let metrics = calculate_metrics(&pool, TEST_PROJECT, date, EvMethod::ZeroHundred)
+            .await
             .unwrap();
-        week_numbers.push(compound_week);
-        current_date += Duration::weeks(1);
+        assert_eq!(metrics.budget_at_completion, 20.0);
+        assert_eq!(metrics.planned_value, 5.0);
+        assert_eq!(metrics.earned_value, 5.0);
+        assert_eq!(metrics.actual_cost, 8.0);
+        assert_eq!(metrics.schedule_variance, 0.0);
+        assert_eq!(metrics.cost_variance, -3.0);
+        assert_eq!(metrics.schedule_performance_index, Some(1.0));
+        assert_eq!(metrics.cost_performance_index, Some(0.625));
+        assert_eq!(metrics.estimate_at_completion, Some(32.0));
+        assert_eq!(metrics.estimate_to_complete, Some(24.0));
+        assert_eq!(metrics.variance_at_completion, Some(-12.0));
+        assert_eq!(metrics.to_complete_performance_index, Some(1.25));
+    }
+
+    #[tokio::test]
+    async fn earns_work_following_the_method() {
+        let pool = test_pool().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack)
+            VALUES (1, 1, 'Design', 4, '2026-10-19', '2026-10-22', 0),
+                   (1, 2, 'Build', 6, '2026-10-22', '2026-10-29', 0),
+                   (1, 3, 'Test', 2, '2026-10-30', '2026-11-02', 0);
+            INSERT INTO task_data (project_id, task_id, finished_at)
+            VALUES (1, 1, '2026-10-23 17:00:00');
+            INSERT INTO timesheet (project_id, task_id, date, duration)
+            VALUES (1, 1, '2026-10-19', 4),
+                   (1, 2, '2026-10-22', 2);
+            INSERT INTO task_progress (project_id, task_id, date, percent_complete)
+            VALUES (1, 1, '2026-10-20', 25),
+                   (1, 1, '2026-10-21', 50),
+                   (1, 2, '2026-10-23', 50);
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let date = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+        let earned = |method| {
+            let pool = pool.clone();
+            async move {
+                let mut earned = earned_days(&pool, TEST_PROJECT, method).await.unwrap();
+                earned.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                earned
+            }
+        };
+
+        // All of the work once the task is finished
+        assert_eq!(earned(EvMethod::ZeroHundred).await, vec![(date(23), 4.0)]);
+        // Half when the first work or progress shows up, the rest when finished
+        assert_eq!(
+            earned(EvMethod::FiftyFifty).await,
+            vec![(date(19), 2.0), (date(22), 3.0), (date(23), 2.0)]
+        );
+        // Each progress update earns its share, finishing earns what is left
+        assert_eq!(
+            earned(EvMethod::PercentComplete).await,
+            vec![
+                (date(20), 1.0),
+                (date(21), 1.0),
+                (date(23), 2.0),
+                (date(23), 3.0)
+            ]
+        );
+
+        let metrics = calculate_metrics(&pool, TEST_PROJECT, date(22), EvMethod::FiftyFifty)
+            .await
+            .unwrap();
+        assert_eq!(metrics.earned_value, 5.0);
+    }
+
+    #[tokio::test]
+    async fn empty_project_has_no_metrics() {
+        let pool = test_pool().await;
+        let date = NaiveDate::from_ymd_opt(2026, 10, 23).unwrap();
+
+        let error = calculate_metrics(&pool, TEST_PROJECT, date, EvMethod::ZeroHundred)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "No tasks to report on, import a plan first"
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_project_has_no_chart() {
+        let pool = test_pool().await;
+        let output = ChartOutput {
+            format: None,
+            out_file: None,
+            open: false,
+            settings: Default::default(),
+        };
+
+        let error = generate_chart(
+            &pool,
+            TEST_PROJECT,
+            &Config::default(),
+            "Empty",
+            &[],
+            EvMethod::ZeroHundred,
+            &output,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(error.to_string(), "No tasks to chart, import a plan first");
     }
-    week_numbers
 }
@@ -0,0 +1,360 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::bail;
+use chrono::{Local, NaiveDate};
+use colored::Colorize;
+use prettytable::{row, Table};
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::config::{Config, SlackThresholds};
+use crate::project::risk::FloatBucket;
+use crate::project::{dependency, dfmt, slack_color};
+
+// Task of the plan with its float in the baseline and now
+struct TrackedTask {
+    id: i64,
+    name: String,
+    baseline_slack: i64,
+    slack: i64,
+}
+
+// Record the current float of every task for today, replacing an earlier snapshot of the day
+pub(crate) async fn snapshot(
+    conn: &mut SqliteConnection,
+    project_id: i64,
+    date: NaiveDate,
+) -> anyhow::Result<u64> {
+    let date = date.format("%Y-%m-%d").to_string();
+    sqlx::query!(
+        r#"DELETE FROM float_snapshots WHERE project_id = ?1 AND date = ?2"#,
+        project_id,
+        date
+    )
+    .execute(&mut *conn)
+    .await?;
+    let tasks = sqlx::query!(
+        r#"
+        INSERT INTO float_snapshots (project_id, date, task_id, total_slack)
+        SELECT project_id, ?2, id, total_slack
+        FROM tasks
+        WHERE project_id = ?1
+        "#,
+        project_id,
+        date
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+    Ok(tasks)
+}
+
+pub(crate) async fn save_snapshot(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let today = Local::now().date_naive();
+    let mut conn = pool.acquire().await?;
+    let tasks = snapshot(&mut conn, project_id, today).await?;
+    println!(
+        "📸 Recorded the float of {} tasks on {}",
+        tasks,
+        dfmt(today, &config.dates)
+    );
+    Ok(())
+}
+
+// Float burnt by each chain of non-critical tasks since the baseline, and the tasks that have
+// become near-critical since with the day they crossed into the red
+pub(crate) async fn report(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    baseline: &Option<String>,
+) -> anyhow::Result<()> {
+    let baseline = match baseline {
+        Some(name) => name.clone(),
+        None => {
+            let latest = sqlx::query!(
+                r#"
+                SELECT name as "name!"
+                FROM baselines
+                WHERE project_id = ?1
+                ORDER BY created_at DESC, id DESC
+                LIMIT 1
+                "#,
+                project_id
+            )
+            .fetch_optional(pool)
+            .await?;
+            match latest {
+                Some(latest) => latest.name,
+                None => bail!("No baselines to compare with, save one with `baseline save`"),
+            }
+        }
+    };
+
+    // Unfinished tasks that had float in the baseline, milestones are left out
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.id           as "id!",
+               t.name         as name,
+               bt.total_slack as baseline_slack,
+               t.total_slack  as slack
+        FROM tasks t
+                 JOIN baseline_tasks bt ON bt.task_id = t.id
+                 JOIN baselines b ON b.id = bt.baseline_id
+                 LEFT OUTER JOIN task_data td ON td.project_id = t.project_id AND td.task_id = t.id
+        WHERE t.project_id = ?1
+          AND b.project_id = ?1
+          AND b.name = ?2
+          AND t.duration > 0
+          AND bt.total_slack > 0
+          AND td.finished_at IS NULL
+        ORDER BY bt.start_date, t.id
+        "#,
+        project_id,
+        baseline
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut tasks = vec![];
+    for row in rows {
+        tasks.push(TrackedTask {
+            id: row.id,
+            name: row.name,
+            baseline_slack: row.baseline_slack,
+            slack: row.slack,
+        });
+    }
+    if tasks.is_empty() {
+        println!(
+            "✨ No open tasks had float in baseline {}, nothing to track",
+            baseline
+        );
+        return Ok(());
+    }
+
+    let chains = chains(&tasks, &dependency::load(pool, project_id).await?);
+    let mut table = Table::new();
+    table.add_row(row![
+        "Chain".bold(),
+        "Baseline Float".bold(),
+        "Float Now".bold(),
+        "Burnt".bold(),
+    ]);
+    for chain in &chains {
+        // The float of a chain is that of its most critical task
+        let baseline_float = chain
+            .iter()
+            .map(|task| task.baseline_slack)
+            .min()
+            .unwrap_or(0);
+        let float = chain.iter().map(|task| task.slack).min().unwrap_or(0);
+        let burnt = baseline_float - float;
+        table.add_row(row![
+            chain
+                .iter()
+                .map(|task| format!("#{} {}", task.id, task.name))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            baseline_float.to_string(),
+            float.to_string().color(slack_color(float, &config.slack)),
+            if burnt > 0 {
+                burnt.to_string().red()
+            } else {
+                burnt.to_string().normal()
+            },
+        ]);
+    }
+    println!("🔥 Float burnt since baseline {}", baseline);
+    table.printstd();
+
+    // Tasks that were safe in the baseline and are close to the critical path now
+    let near_critical: Vec<&TrackedTask> = tasks
+        .iter()
+        .filter(|task| {
+            is_near_critical(task.slack, &config.slack)
+                && !is_near_critical(task.baseline_slack, &config.slack)
+        })
+        .collect();
+    if near_critical.is_empty() {
+        println!(
+            "✅ No task has become near-critical since baseline {}",
+            baseline
+        );
+        return Ok(());
+    }
+
+    let history = snapshots(pool, project_id).await?;
+    let mut table = Table::new();
+    table.add_row(row![
+        "#".bold(),
+        "Task".bold(),
+        "Baseline Float".bold(),
+        "Float Now".bold(),
+        "Near-critical Since".bold(),
+    ]);
+    for task in near_critical {
+        table.add_row(row![
+            task.id.to_string(),
+            task.name,
+            task.baseline_slack.to_string(),
+            task.slack
+                .to_string()
+                .color(slack_color(task.slack, &config.slack)),
+            crossed_on(history.get(&task.id), &config.slack)
+                .map(|date| dfmt(date, &config.dates))
+                .unwrap_or("--".to_string()),
+        ]);
+    }
+    println!("⚠️ Tasks that have become near-critical");
+    table.printstd();
+    Ok(())
+}
+
+fn is_near_critical(slack: i64, thresholds: &SlackThresholds) -> bool {
+    matches!(
+        FloatBucket::of(slack, thresholds),
+        FloatBucket::Critical | FloatBucket::Red
+    )
+}
+
+// Linked tasks that shared their float in the baseline form a chain, each chain is ordered by
+// the baseline start of its tasks
+fn chains<'a>(
+    tasks: &'a [TrackedTask],
+    links: &HashMap<i64, Vec<dependency::Link>>,
+) -> Vec<Vec<&'a TrackedTask>> {
+    let baseline_slack: HashMap<i64, i64> = tasks
+        .iter()
+        .map(|task| (task.id, task.baseline_slack))
+        .collect();
+    let mut neighbours: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (task, links) in links {
+        for link in links {
+            let (Some(slack), Some(predecessor_slack)) = (
+                baseline_slack.get(task),
+                baseline_slack.get(&link.predecessor),
+            ) else {
+                continue;
+            };
+            if slack == predecessor_slack {
+                neighbours.entry(*task).or_default().push(link.predecessor);
+                neighbours.entry(link.predecessor).or_default().push(*task);
+            }
+        }
+    }
+
+    let mut chain_of: HashMap<i64, usize> = HashMap::new();
+    let mut chains: Vec<Vec<&TrackedTask>> = vec![];
+    for task in tasks {
+        if chain_of.contains_key(&task.id) {
+            continue;
+        }
+        let chain = chains.len();
+        let mut pending = vec![task.id];
+        while let Some(id) = pending.pop() {
+            if chain_of.insert(id, chain).is_none() {
+                pending.extend(neighbours.get(&id).into_iter().flatten());
+            }
+        }
+        chains.push(vec![]);
+    }
+    // Tasks are in baseline order, so are the chains and the tasks on them
+    for task in tasks {
+        chains[chain_of[&task.id]].push(task);
+    }
+    chains
+}
+
+// Recorded float of every task by day
+async fn snapshots(
+    pool: &SqlitePool,
+    project_id: i64,
+) -> anyhow::Result<HashMap<i64, BTreeMap<NaiveDate, i64>>> {
+    let rows = sqlx::query!(
+        r#"SELECT date, task_id, total_slack FROM float_snapshots WHERE project_id = ?1"#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut snapshots: HashMap<i64, BTreeMap<NaiveDate, i64>> = HashMap::new();
+    for row in rows {
+        snapshots
+            .entry(row.task_id)
+            .or_default()
+            .insert(row.date.parse()?, row.total_slack);
+    }
+    Ok(snapshots)
+}
+
+// First day of the latest run of snapshots in which the task was near-critical
+fn crossed_on(
+    history: Option<&BTreeMap<NaiveDate, i64>>,
+    thresholds: &SlackThresholds,
+) -> Option<NaiveDate> {
+    let mut crossed = None;
+    for (date, slack) in history.into_iter().flatten() {
+        if !is_near_critical(*slack, thresholds) {
+            crossed = None;
+        } else if crossed.is_none() {
+            crossed = Some(*date);
+        }
+    }
+    crossed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::dependency::{Link, LinkType};
+
+    fn tracked(id: i64, baseline_slack: i64) -> TrackedTask {
+        TrackedTask {
+            id,
+            name: format!("Task {}", id),
+            baseline_slack,
+            slack: baseline_slack,
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn linked_tasks_sharing_float_form_a_chain() {
+        let tasks = vec![
+            tracked(1, 0),
+            tracked(2, 5),
+            tracked(3, 0),
+            tracked(4, 5),
+            tracked(5, 20),
+        ];
+        let after = |predecessor| vec![Link::new(predecessor, LinkType::FinishToStart, 0)];
+        let links = HashMap::from([(2, after(1)), (3, after(1)), (4, after(2)), (5, after(4))]);
+
+        let ids: Vec<Vec<i64>> = chains(&tasks, &links)
+            .iter()
+            .map(|chain| chain.iter().map(|task| task.id).collect())
+            .collect();
+        assert_eq!(ids, vec![vec![1, 3], vec![2, 4], vec![5]]);
+    }
+
+    #[test]
+    fn crossing_dates_from_the_latest_near_critical_run() {
+        let thresholds = SlackThresholds::default();
+        let history = BTreeMap::from([(date(5), 4), (date(12), 25), (date(19), 10), (date(26), 0)]);
+        assert_eq!(crossed_on(Some(&history), &thresholds), Some(date(19)));
+
+        let recovered = BTreeMap::from([(date(5), 4), (date(12), 25)]);
+        assert_eq!(crossed_on(Some(&recovered), &thresholds), None);
+        assert_eq!(crossed_on(None, &thresholds), None);
+        // A lower red threshold no longer counts 10 days of float as near-critical
+        let strict = SlackThresholds { red: 5, yellow: 30 };
+        assert_eq!(crossed_on(Some(&history), &strict), Some(date(26)));
+    }
+}
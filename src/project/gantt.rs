@@ -0,0 +1,395 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use plotly::color::NamedColor::{DarkGray, Gray, LightGray, Red, SteelBlue};
+use plotly::common::{DashType, Marker, MarkerSymbol, Mode, Title};
+use plotly::layout::{Annotation, Axis, Shape, ShapeLine, ShapeType};
+use plotly::{common, Layout, Plot, Scatter};
+use sqlx::{Pool, Sqlite};
+use titlecase::titlecase;
+
+use crate::config::Config;
+use crate::project::chart::{export_chart, ChartOutput};
+use crate::project::dependency::{self, Link, LinkType};
+use crate::project::dfmt;
+
+// Thickness of the task bars in pixels
+const BAR_WIDTH: f64 = 14.0;
+
+struct GanttTask {
+    id: i64,
+    name: String,
+    duration: i64,
+    start_date: NaiveDate,
+    finish_date: NaiveDate,
+    total_slack: i64,
+    predecessors: Vec<Link>,
+    finished: bool,
+    assignee: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum BarKind {
+    Completed,
+    Critical,
+    Normal,
+}
+
+impl BarKind {
+    fn of(task: &GanttTask) -> Self {
+        if task.finished {
+            BarKind::Completed
+        } else if task.total_slack <= 0 {
+            BarKind::Critical
+        } else {
+            BarKind::Normal
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            BarKind::Completed => "Completed",
+            BarKind::Critical => "Critical path",
+            BarKind::Normal => "Task",
+        }
+    }
+}
+
+// Tasks as horizontal bars in plan order, with arrows from each predecessor to its successor
+pub(crate) async fn generate_chart(
+    pool: &Pool<Sqlite>,
+    project_id: i64,
+    config: &Config,
+    chart_title: &str,
+    output: &ChartOutput,
+) -> anyhow::Result<()> {
+    let tasks = load_tasks(pool, project_id).await?;
+    if tasks.is_empty() {
+        bail!("No tasks to chart, import a plan first");
+    }
+
+    // The first task is on top, so rows count down from zero
+    let rows: HashMap<i64, f64> = tasks
+        .iter()
+        .enumerate()
+        .map(|(i, task)| (task.id, -(i as f64)))
+        .collect();
+
+    let mut plot = Plot::new();
+    let mut in_legend = vec![];
+    for task in &tasks {
+        let kind = BarKind::of(task);
+        let color = match kind {
+            BarKind::Completed => LightGray,
+            BarKind::Critical => Red,
+            BarKind::Normal => SteelBlue,
+        };
+        let hover_text = format!(
+            "#{} {}<br>{} – {}<br>{}Slack: {} days",
+            task.id,
+            task.name,
+            dfmt(task.start_date, &config.dates),
+            dfmt(task.finish_date, &config.dates),
+            task.assignee
+                .as_ref()
+                .map(|assignee| format!("{}<br>", titlecase(assignee)))
+                .unwrap_or_default(),
+            task.total_slack
+        );
+        let row = rows[&task.id];
+
+        // Milestones take no time and are shown as a diamond
+        let trace = if task.duration == 0 {
+            Scatter::new(bar_dates(task), vec![row])
+                .mode(Mode::Markers)
+                .marker(
+                    Marker::new()
+                        .symbol(MarkerSymbol::Diamond)
+                        .size(BAR_WIDTH as usize)
+                        .color(color),
+                )
+        } else {
+            Scatter::new(bar_dates(task), vec![row, row])
+                .mode(Mode::Lines)
+                .line(common::Line::new().color(color).width(BAR_WIDTH))
+        };
+        let trace = trace
+            .name(kind.name())
+            .legend_group(kind.name())
+            .show_legend(!in_legend.contains(&kind))
+            .hover_text(hover_text)
+            .hover_info(common::HoverInfo::Text);
+        in_legend.push(kind);
+        plot.add_trace(trace);
+    }
+
+    let mut layout = Layout::new()
+        .title(Title::new(chart_title))
+        .x_axis(Axis::new().type_(plotly::layout::AxisType::Date))
+        .y_axis(
+            Axis::new()
+                .tick_values(tasks.iter().map(|task| rows[&task.id]).collect())
+                .tick_text(
+                    tasks
+                        .iter()
+                        .map(|task| format!("#{} {}", task.id, task.name))
+                        .collect(),
+                )
+                .show_grid(false)
+                .zero_line(false),
+        );
+
+    for arrow in arrows(&tasks) {
+        layout.add_annotation(
+            Annotation::new()
+                .x_ref("x")
+                .y_ref("y")
+                .x(date_label(arrow.to_date))
+                .y(rows[&arrow.to])
+                .ax_ref("x")
+                .ay_ref("y")
+                .ax(date_label(arrow.from_date))
+                .ay(rows[&arrow.from])
+                .text("")
+                .show_arrow(true)
+                .arrow_head(2)
+                .arrow_width(1.0)
+                .arrow_color(if arrow.finished { LightGray } else { DarkGray }),
+        );
+    }
+
+    let today = date_label(Local::now().date_naive());
+    layout.add_shape(
+        Shape::new()
+            .shape_type(ShapeType::Line)
+            .x_ref("x")
+            .y_ref("paper")
+            .x0(today.clone())
+            .x1(today.clone())
+            .y0(0)
+            .y1(1)
+            .line(ShapeLine::new().color(Gray).width(2.0).dash(DashType::Dash)),
+    );
+    layout.add_annotation(
+        Annotation::new()
+            .x_ref("x")
+            .y_ref("paper")
+            .x(today)
+            .y(1)
+            .text("Today")
+            .show_arrow(false),
+    );
+    plot.set_layout(layout);
+
+    println!(
+        "📊 Gantt chart of {} tasks, {} on the critical path",
+        tasks.len(),
+        tasks
+            .iter()
+            .filter(|task| BarKind::of(task) == BarKind::Critical)
+            .count()
+    );
+    export_chart(&plot, output, "gantt_chart", "Gantt Chart")
+}
+
+async fn load_tasks(pool: &Pool<Sqlite>, project_id: i64) -> anyhow::Result<Vec<GanttTask>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.id           as "id!",
+               t.name         as name,
+               t.duration     as duration,
+               t.start_date   as start_date,
+               t.finish_date  as finish_date,
+               t.total_slack  as total_slack,
+               td.finished_at as finished_at,
+               tm.name        as "assignee?"
+        FROM tasks t
+                 LEFT OUTER JOIN task_data td ON td.project_id = t.project_id AND td.task_id = t.id
+                 LEFT OUTER JOIN team tm ON tm.id = td.member_id
+        WHERE t.project_id = ?1
+        ORDER BY t.id
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut links = dependency::load(pool, project_id).await?;
+    let mut tasks = vec![];
+    for row in rows {
+        // Completed tasks are drawn until the day they were actually finished
+        let finished_at = row
+            .finished_at
+            .and_then(|date_str| NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").ok())
+            .map(|naive_datetime| naive_datetime.date());
+        let start_date: NaiveDate = row.start_date.parse()?;
+        tasks.push(GanttTask {
+            id: row.id,
+            name: row.name,
+            duration: row.duration,
+            start_date,
+            finish_date: finished_at
+                .map(|date| date.max(start_date))
+                .unwrap_or(row.finish_date.parse()?),
+            total_slack: row.total_slack,
+            predecessors: links.remove(&row.id).unwrap_or_default(),
+            finished: finished_at.is_some(),
+            assignee: row.assignee,
+        });
+    }
+    Ok(tasks)
+}
+
+// Dates a bar is drawn between, bars end after the last day worked
+fn bar_dates(task: &GanttTask) -> Vec<String> {
+    if task.duration == 0 {
+        vec![date_label(task.start_date)]
+    } else {
+        vec![date_label(task.start_date), date_label(bar_end(task))]
+    }
+}
+
+// Bars end after the last day worked, milestones on their day
+fn bar_end(task: &GanttTask) -> NaiveDate {
+    if task.duration == 0 {
+        task.finish_date
+    } else {
+        task.finish_date + Duration::days(1)
+    }
+}
+
+// Arrow from the linked end of a predecessor to the linked end of its successor
+struct Arrow {
+    from: i64,
+    from_date: NaiveDate,
+    to: i64,
+    to_date: NaiveDate,
+    finished: bool,
+}
+
+fn arrows(tasks: &[GanttTask]) -> Vec<Arrow> {
+    let mut arrows = vec![];
+    for task in tasks {
+        for link in &task.predecessors {
+            let Some(predecessor) = tasks.iter().find(|t| t.id == link.predecessor) else {
+                continue;
+            };
+            let (from_date, to_date) = match link.link_type {
+                LinkType::FinishToStart => (bar_end(predecessor), task.start_date),
+                LinkType::StartToStart => (predecessor.start_date, task.start_date),
+                LinkType::FinishToFinish => (bar_end(predecessor), bar_end(task)),
+                LinkType::StartToFinish => (predecessor.start_date, bar_end(task)),
+            };
+            arrows.push(Arrow {
+                from: predecessor.id,
+                from_date,
+                to: task.id,
+                to_date,
+                finished: task.finished,
+            });
+        }
+    }
+    arrows
+}
+
+fn date_label(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{test_pool, TEST_PROJECT};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    async fn pool_with_plan() -> Pool<Sqlite> {
+        let pool = test_pool().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack)
+            VALUES (1, 1, 'Start', 0, '2026-10-19', '2026-10-19', 0),
+                   (1, 2, 'Design', 5, '2026-10-19', '2026-10-23', 0),
+                   (1, 3, 'Docs', 2, '2026-10-21', '2026-10-22', 3),
+                   (1, 4, 'Build', 3, '2026-10-26', '2026-10-28', 0);
+            INSERT INTO dependencies (project_id, task_id, predecessor_id, link_type, lag)
+            VALUES (1, 2, 1, 'FS', 0),
+                   (1, 3, 2, 'SS', 2),
+                   (1, 4, 2, 'FS', 0),
+                   (1, 4, 3, 'FF', 4);
+            INSERT INTO team (id, name) VALUES (1, 'dana');
+            INSERT INTO task_data (project_id, task_id, member_id, finished_at)
+            VALUES (1, 2, 1, '2026-10-22 16:00:00');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn finished_tasks_end_when_they_were_finished() {
+        let pool = pool_with_plan().await;
+        let tasks = load_tasks(&pool, TEST_PROJECT).await.unwrap();
+
+        let kinds: Vec<&str> = tasks.iter().map(|task| BarKind::of(task).name()).collect();
+        assert_eq!(
+            kinds,
+            vec!["Critical path", "Completed", "Task", "Critical path"]
+        );
+        assert_eq!(tasks[1].finish_date, date(22));
+        assert_eq!(tasks[1].assignee.as_deref(), Some("dana"));
+        assert_eq!(bar_dates(&tasks[1]), vec!["2026-10-19", "2026-10-23"]);
+        // Milestones are a single point
+        assert_eq!(bar_dates(&tasks[0]), vec!["2026-10-19"]);
+    }
+
+    #[tokio::test]
+    async fn arrows_join_the_linked_ends() {
+        let pool = pool_with_plan().await;
+        let tasks = load_tasks(&pool, TEST_PROJECT).await.unwrap();
+
+        let arrows: Vec<(i64, NaiveDate, i64, NaiveDate, bool)> = arrows(&tasks)
+            .iter()
+            .map(|arrow| {
+                (
+                    arrow.from,
+                    arrow.from_date,
+                    arrow.to,
+                    arrow.to_date,
+                    arrow.finished,
+                )
+            })
+            .collect();
+        assert_eq!(
+            arrows,
+            vec![
+                (1, date(19), 2, date(19), true),
+                (2, date(19), 3, date(21), false),
+                (2, date(23), 4, date(26), false),
+                (3, date(23), 4, date(29), false),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_project_has_no_chart() {
+        let pool = test_pool().await;
+        let output = ChartOutput {
+            format: None,
+            out_file: None,
+            open: false,
+            settings: Default::default(),
+        };
+
+        let error = generate_chart(&pool, TEST_PROJECT, &Config::default(), "Empty", &output)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "No tasks to chart, import a plan first");
+    }
+}
@@ -1,145 +1,343 @@
-use std::io::BufRead;
-use std::str::FromStr;
+use std::collections::HashMap;
+use std::path::Path;
 
-use chrono::{NaiveDate, NaiveDateTime};
-use colored::Colorize;
+use anyhow::{anyhow, bail};
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use clap::ValueEnum;
+use colored::{Color, Colorize};
 use csv::Reader;
 use inquire::error::InquireResult;
 use inquire::list_option::ListOption;
+use inquire::validator::Validation;
 use inquire::{DateSelect, MultiSelect, Select};
-use prettytable::{row, Table};
+use prettytable::{row, Cell, Row, Table};
+use promptly::prompt_default;
 use serde::de::Error;
-use serde::Deserialize;
-use sqlx::{Pool, Sqlite, SqlitePool};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Sqlite, SqliteConnection, SqlitePool};
 use titlecase::titlecase;
 
+use crate::config::{Config, DateFormats, SlackThresholds};
+
+pub(crate) mod allocation;
+pub(crate) mod baseline;
+pub(crate) mod calendar;
+pub(crate) mod chart;
+pub(crate) mod dashboard;
+pub(crate) mod dependency;
 pub(crate) mod earned_value;
+pub(crate) mod float;
+pub(crate) mod gantt;
+mod mspdi;
+pub(crate) mod projects;
+pub(crate) mod risk;
+pub(crate) mod schedule;
+pub(crate) mod staffing;
+pub(crate) mod sync;
+pub(crate) mod team;
+pub(crate) mod timeline;
+pub(crate) mod timesheet;
+pub(crate) mod validate;
 
 pub(crate) async fn list(
     pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
     task_status: TaskStatus,
     number_of_tasks: &Option<usize>,
+    baseline: &Option<String>,
+    output: ListFormat,
 ) -> anyhow::Result<()> {
-    let tasks = get_tasks(pool.clone(), task_status).await?;
+    let tasks = get_tasks(pool.clone(), project_id, task_status).await?;
+
+    let baseline_finish_dates = match baseline {
+        Some(name) => Some(baseline::finish_dates(pool, project_id, name).await?),
+        None => None,
+    };
 
     let tasks = match number_of_tasks {
         Some(n) => tasks.into_iter().take(*n).collect(),
         None => tasks,
     };
+    let calendar = calendar::project_calendar(pool, project_id).await?;
 
-    // give each assignee a color based on assignees in tasks
-    let assignees = tasks
-        .iter()
-        .map(|task| task.assignee.clone().unwrap_or("".to_string()))
-        .collect::<Vec<String>>();
+    match output {
+        ListFormat::Table => {}
+        ListFormat::Json => {
+            println!("{}", list_json(&tasks, &baseline_finish_dates, &calendar)?);
+            return Ok(());
+        }
+        ListFormat::Csv => {
+            print!("{}", list_csv(&tasks, &baseline_finish_dates, &calendar)?);
+            return Ok(());
+        }
+        ListFormat::Markdown => {
+            print!(
+                "{}",
+                list_markdown(&tasks, &baseline_finish_dates, &calendar, &config.dates)
+            );
+            return Ok(());
+        }
+    }
 
     // Create the table
     let mut table = Table::new();
-    table.add_row(row![
-        "#".bold(),
-        "Assignee".bold(),
-        "Task".bold(),
-        "Estimated Duration".bold(),
-        "Slack".bold(),
-        "Planned Start Date".bold(),
-        "Planned Finish Date".bold(),
-        "Actual Finish Date".bold(),
-        "Predecessors".bold(),
-    ]);
-    for task in tasks {
-        let assignee = titlecase(&*task.assignee.unwrap_or("".to_string()));
-
-        let predecessor_string = task
-            .predecessors
+    table.add_row(Row::new(
+        list_headers(baseline_finish_dates.is_some())
             .iter()
-            .map(|n| n.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let finished_at_string = match task.finished_at {
-            Some(date) => dfmt(date),
-            None => "--".to_string(),
-        };
-
-        let start_date = dfmt(task.start_date);
-        let finish_date = dfmt(task.finish_date);
-
-        let slack = if (task.slack <= 10) {
-            task.slack.to_string().red()
-        } else if task.slack <= 30 {
-            task.slack.to_string().yellow()
-        } else {
-            task.slack.to_string().green()
-        };
+            .map(|column| Cell::new(&column.bold().to_string()))
+            .collect(),
+    ));
+    for task in tasks {
+        let [id, assignee, name, duration, slack, percent_complete, start_date, finish_date, finished_at_string, predecessor_string] =
+            list_cells(&task, &config.dates);
+        let slack = slack.color(slack_color(task.slack, &config.slack));
 
-        if task.finished {
-            table.add_row(row![
-                task.id.to_string().dimmed(),
+        let mut row = if task.finished {
+            row![
+                id.dimmed(),
                 assignee,
-                task.name.green().dimmed(),
-                task.duration.to_string().dimmed(),
+                name.green().dimmed(),
+                duration.dimmed(),
                 slack.dimmed(),
+                percent_complete.dimmed(),
                 start_date.dimmed(),
                 finish_date.dimmed(),
                 finished_at_string.dimmed(),
                 predecessor_string.dimmed()
-            ]);
-        } else if assignee != "" {
-            table.add_row(row![
-                task.id.to_string().bold(),
+            ]
+        } else if !assignee.is_empty() {
+            row![
+                id.bold(),
                 assignee,
-                task.name.blue(),
-                task.duration.to_string(),
+                name.blue(),
+                duration,
                 slack,
+                percent_complete,
                 start_date,
-                finish_date.to_string(),
+                finish_date,
                 finished_at_string,
                 predecessor_string
-            ]);
+            ]
         } else {
-            table.add_row(row![
-                task.id.to_string(),
+            row![
+                id,
                 assignee,
-                task.name,
-                task.duration.to_string(),
+                name,
+                duration,
                 slack,
-                start_date.to_string(),
-                finish_date.to_string(),
+                percent_complete,
+                start_date,
+                finish_date,
                 finished_at_string,
                 predecessor_string
-            ]);
+            ]
         };
+
+        // Compare against the baseline, positive variance means the task finishes later
+        if let Some(finish_dates) = &baseline_finish_dates {
+            let (baseline_finish, variance) = match finish_dates.get(&task.id) {
+                Some(baseline_finish) => {
+                    let variance = calendar.workdays_between(*baseline_finish, task.finish_date);
+                    let variance = if variance > 0 {
+                        format!("+{}", variance).red()
+                    } else {
+                        variance.to_string().green()
+                    };
+                    (dfmt(*baseline_finish, &config.dates), variance)
+                }
+                None => ("--".to_string(), "new".yellow()),
+            };
+            row.add_cell(Cell::new(&baseline_finish));
+            row.add_cell(Cell::new(&variance.to_string()));
+        }
+        table.add_row(row);
     }
     table.printstd();
     Ok(())
 }
 
-async fn get_tasks(pool: Pool<Sqlite>, task_status: TaskStatus) -> anyhow::Result<Vec<Task>> {
-    // Insert the task, then obtain the ID of this row
+fn list_json(
+    tasks: &[Task],
+    baseline_finish_dates: &Option<HashMap<i64, NaiveDate>>,
+    calendar: &calendar::Calendar,
+) -> anyhow::Result<String> {
+    let listed: Vec<ListedTask> = tasks
+        .iter()
+        .map(|task| {
+            let baseline_finish_date = baseline_finish_dates
+                .as_ref()
+                .and_then(|finish_dates| finish_dates.get(&task.id).copied());
+            ListedTask {
+                task,
+                baseline_finish_date,
+                finish_variance: baseline_finish_date.map(|baseline_finish| {
+                    calendar.workdays_between(baseline_finish, task.finish_date)
+                }),
+            }
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&listed)?)
+}
+
+fn list_csv(
+    tasks: &[Task],
+    baseline_finish_dates: &Option<HashMap<i64, NaiveDate>>,
+    calendar: &calendar::Calendar,
+) -> anyhow::Result<String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer.write_record(list_headers(baseline_finish_dates.is_some()))?;
+    for task in tasks {
+        writer.write_record(list_record(
+            task,
+            baseline_finish_dates,
+            calendar,
+            &|date| date.to_string(),
+        ))?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+fn list_markdown(
+    tasks: &[Task],
+    baseline_finish_dates: &Option<HashMap<i64, NaiveDate>>,
+    calendar: &calendar::Calendar,
+    dates: &DateFormats,
+) -> String {
+    let headers = list_headers(baseline_finish_dates.is_some());
+    let mut markdown = format!("| {} |\n", headers.join(" | "));
+    markdown.push_str(&format!("|{}\n", "---|".repeat(headers.len())));
+    for task in tasks {
+        let record = list_record(task, baseline_finish_dates, calendar, &|date| {
+            dfmt(date, dates)
+        })
+        .iter()
+        .map(|value| value.replace('|', "\\|"))
+        .collect::<Vec<String>>();
+        markdown.push_str(&format!("| {} |\n", record.join(" | ")));
+    }
+    markdown
+}
+
+// Columns of the `list` table, the dashboard shows the same
+const LIST_COLUMNS: [&str; 10] = [
+    "#",
+    "Assignee",
+    "Task",
+    "Estimated Duration",
+    "Slack",
+    "Done",
+    "Planned Start Date",
+    "Planned Finish Date",
+    "Actual Finish Date",
+    "Predecessors",
+];
+
+// Unstyled cells of the `list` table, matching `LIST_COLUMNS`
+fn list_cells(task: &Task, dates: &DateFormats) -> [String; 10] {
+    [
+        task.id.to_string(),
+        titlecase(task.assignee.as_deref().unwrap_or_default()),
+        task.name.clone(),
+        task.duration.to_string(),
+        task.slack.to_string(),
+        format!("{} %", task.percent_complete),
+        dfmt(task.start_date, dates),
+        dfmt(task.finish_date, dates),
+        task.finished_at
+            .map(|date| dfmt(date, dates))
+            .unwrap_or("--".to_string()),
+        dependency::format_links(&task.predecessors),
+    ]
+}
+
+// Columns of every `list` output, the table ones followed by the baseline comparison
+fn list_headers(with_baseline: bool) -> Vec<&'static str> {
+    let mut headers = LIST_COLUMNS.to_vec();
+    if with_baseline {
+        headers.push("Baseline Finish Date");
+        headers.push("Finish Variance");
+    }
+    headers
+}
+
+// One row of the CSV and Markdown output, matching `list_headers`. Values are left plain so
+// they can be read back, e.g. the percent sign is left out.
+fn list_record(
+    task: &Task,
+    baseline_finish_dates: &Option<HashMap<i64, NaiveDate>>,
+    calendar: &calendar::Calendar,
+    date_format: &dyn Fn(NaiveDate) -> String,
+) -> Vec<String> {
+    let mut record = vec![
+        task.id.to_string(),
+        task.assignee.clone().unwrap_or_default(),
+        task.name.clone(),
+        task.duration.to_string(),
+        task.slack.to_string(),
+        task.percent_complete.to_string(),
+        date_format(task.start_date),
+        date_format(task.finish_date),
+        task.finished_at.map(date_format).unwrap_or_default(),
+        dependency::format_links(&task.predecessors),
+    ];
+    if let Some(finish_dates) = baseline_finish_dates {
+        match finish_dates.get(&task.id) {
+            Some(baseline_finish) => {
+                record.push(date_format(*baseline_finish));
+                record.push(
+                    calendar
+                        .workdays_between(*baseline_finish, task.finish_date)
+                        .to_string(),
+                );
+            }
+            None => {
+                record.push(String::new());
+                record.push(String::new());
+            }
+        }
+    }
+    record
+}
+
+async fn get_tasks(
+    pool: Pool<Sqlite>,
+    project_id: i64,
+    task_status: TaskStatus,
+) -> anyhow::Result<Vec<Task>> {
     let tasks = sqlx::query!(
         r#"
-    SELECT t.id           as id,
+    SELECT t.id           as "id!",
        t.name             as name,
        t.duration         as duration,
-       t.predecessors     as predecessors,
        t.start_date       as start_date,
        t.finish_date      as finish_date,
        t.total_slack      as total_slack,
        t.resource_names   as resource_names,
        t.pdex_criticality as pdex_criticality,
        td.id              as task_data_id,
-       td.assignee        as assignee,
+       lower(tm.name)     as "assignee: String",
        td.finished_at     as finished_at,
+       (SELECT tp.percent_complete
+        FROM task_progress tp
+        WHERE tp.project_id = t.project_id
+          AND tp.task_id = t.id
+        ORDER BY tp.date DESC, tp.id DESC
+        LIMIT 1)          as "percent_complete?: i64",
        CASE
            WHEN td.finished_at IS NOT NULL THEN true
            ELSE false
-       END as finished
+       END as "finished!: i32"
        FROM tasks t
          LEFT OUTER JOIN task_data td
-                         ON t.id = td.task_id
-       WHERE duration > 0
+                         ON td.project_id = t.project_id AND td.task_id = t.id
+         LEFT OUTER JOIN team tm
+                         ON tm.id = td.member_id
+       WHERE t.project_id = ?1
+         AND duration > 0
        ORDER BY start_date, total_slack DESC;
     "#,
+        project_id
     )
     .fetch_all(&pool)
     .await?;
@@ -157,22 +355,22 @@ async fn get_tasks(pool: Pool<Sqlite>, task_status: TaskStatus) -> anyhow::Resul
             .collect(),
         TaskStatus::Assigned => tasks
             .into_iter()
-            .filter(|task| task.assignee != None && task.finished == 0)
+            .filter(|task| task.assignee.is_some() && task.finished == 0)
             .collect(),
         TaskStatus::Unassigned => tasks
             .into_iter()
-            .filter(|task| task.assignee == None)
+            .filter(|task| task.assignee.is_none())
             .collect(),
     };
 
     // convert to task type
+    let mut links = dependency::load(&pool, project_id).await?;
     let mut all_tasks: Vec<Task> = vec![];
 
     for t in tasks {
         let finished_at_date = t
             .finished_at
-            .map(|date_str| NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").ok())
-            .flatten()
+            .and_then(|date_str| NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").ok())
             .map(|naive_datetime| naive_datetime.date());
 
         all_tasks.push(Task {
@@ -180,85 +378,245 @@ async fn get_tasks(pool: Pool<Sqlite>, task_status: TaskStatus) -> anyhow::Resul
             name: t.name,
             duration: t.duration,
             slack: t.total_slack,
-            predecessors: t
-                .predecessors
-                .unwrap()
-                .split(',')
-                .map(|s| s.parse::<i64>().unwrap())
-                .collect(),
+            predecessors: links.remove(&t.id).unwrap_or_default(),
             start_date: t.start_date.parse()?,
             finish_date: t.finish_date.parse()?,
             resource_names: t
                 .resource_names
-                .unwrap()
+                .unwrap_or_default()
                 .split(',')
+                .filter(|s| !s.is_empty())
                 .map(|s| s.to_string())
                 .collect(),
             pdex_criticality: t.pdex_criticality.unwrap_or(0),
             assignee: t.assignee,
             finished_at: finished_at_date,
             finished: t.finished == 1,
+            percent_complete: if t.finished == 1 {
+                100
+            } else {
+                t.percent_complete.unwrap_or(0)
+            },
         })
     }
 
     Ok(all_tasks)
 }
-pub async fn log_work(pool: &SqlitePool) -> anyhow::Result<()> {
+pub async fn log_work(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    task_id: Option<i64>,
+    date: Option<NaiveDate>,
+    days: Option<i32>,
+    person: Option<String>,
+) -> anyhow::Result<()> {
+    // Refuse bad input before prompting for anything else
+    if days.is_some_and(|days| days <= 0) {
+        bail!("Logged work must be at least one day");
+    }
+
     // find tasks in progress
-    let selected_task = select_task(
-        get_tasks(pool.clone(), TaskStatus::Assigned).await?,
-        "Select task to log work:",
-    )
-    .expect(
-        "Error when selecting task to log work. Do you have assigned tasks that are in progress?",
-    );
+    let tasks = get_tasks(pool.clone(), project_id, TaskStatus::Assigned).await?;
+    let selected_task = match task_id {
+        Some(id) => tasks
+            .into_iter()
+            .find(|task| task.id == id)
+            .ok_or_else(|| anyhow!("Task #{} is not assigned or already completed", id))?,
+        None => select_task(tasks, "Select task to log work:", &config.dates).expect(
+            "Error when selecting task to log work. Do you have assigned tasks that are in progress?",
+        ),
+    };
 
-    let date = DateSelect::new("Select date: ")
-        .prompt()
-        .expect("Error in date selection")
-        .format("%Y-%m-%d")
-        .to_string();
+    // Work is logged by the assignee unless someone else is named
+    let person = person
+        .map(|person| person.to_lowercase())
+        .or(selected_task.assignee.clone());
 
-    let days = Select::new("Log time worked (in days): ", vec![5, 4, 3, 2, 1])
-        .prompt()
-        .expect("Error in duration selection");
+    // Nobody works on weekends, holidays or their vacation
+    let member_id = team::member_id(pool, person.as_deref()).await?;
+    let calendar = calendar::member_calendar(pool, project_id, member_id).await?;
+    let dates = config.dates.clone();
+    let date = match date {
+        Some(date) if !calendar.is_workday(date) => {
+            bail!("{} is not a working day", dfmt(date, &config.dates))
+        }
+        Some(date) => date,
+        None => DateSelect::new("Select date: ")
+            .with_validator(move |date: NaiveDate| {
+                if calendar.is_workday(date) {
+                    Ok(Validation::Valid)
+                } else {
+                    Ok(Validation::Invalid(
+                        format!("{} is not a working day", dfmt(date, &dates)).into(),
+                    ))
+                }
+            })
+            .prompt()
+            .expect("Error in date selection"),
+    }
+    .format("%Y-%m-%d")
+    .to_string();
+
+    let days = match days {
+        Some(days) => days,
+        None => Select::new("Log time worked (in days): ", vec![5, 4, 3, 2, 1])
+            .prompt()
+            .expect("Error in duration selection"),
+    };
 
     // Insert the task, then obtain the ID of this row
     let id = sqlx::query!(
         r#"
-            INSERT INTO timesheet (task_id, date, duration) VALUES (?1, ?2, ?3)
+            INSERT INTO timesheet (task_id, date, duration, person, member_id, project_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
             "#,
         selected_task.id,
         date,
-        days
+        days,
+        person,
+        member_id,
+        project_id
     )
     .execute(pool)
     .await?
     .last_insert_rowid();
 
     println!(
-        "⌛ Logged time on #{} - {} assigned to {} (rowid: {})",
+        "⌛ Logged time on #{} - {} for {} (rowid: {})",
         selected_task.id,
         selected_task.name,
-        selected_task.assignee.unwrap_or("unknown".to_string()),
-        id.to_string()
+        person.unwrap_or("unknown".to_string()),
+        id
     );
+    schedule::update(pool, project_id, config).await?;
     Ok(())
 }
 
-pub fn track(pool: &SqlitePool) {}
-pub async fn import(pool: &SqlitePool, ms_project_file: String, database_file: String) {
+// Record how far an assigned task has progressed, earned value can be based on it
+pub(crate) async fn update_progress(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    task_id: Option<i64>,
+    percent: Option<u8>,
+    date: Option<NaiveDate>,
+) -> anyhow::Result<()> {
+    let tasks = get_tasks(pool.clone(), project_id, TaskStatus::Assigned).await?;
+    let selected_task = match task_id {
+        Some(id) => tasks
+            .into_iter()
+            .find(|task| task.id == id)
+            .ok_or_else(|| anyhow!("Task #{} is not assigned or already completed", id))?,
+        None => select_task(tasks, "Select task to update progress:", &config.dates)
+            .expect("Error when selecting task. Do you have assigned tasks that are in progress?"),
+    };
+
+    let percent = match percent {
+        Some(percent) => percent,
+        None => prompt_default(
+            format!(
+                "Percent complete of #{} - {}",
+                selected_task.id, selected_task.name
+            ),
+            selected_task.percent_complete as u8,
+        )?,
+    };
+    if percent > 100 {
+        bail!("Percent complete must be between 0 and 100");
+    }
+    if percent == 100 {
+        println!("💡 Use `complete` to finish the task");
+    }
+
+    let date = date
+        .unwrap_or(Local::now().date_naive())
+        .format("%Y-%m-%d")
+        .to_string();
+    sqlx::query!(
+        r#"
+        INSERT INTO task_progress (task_id, date, percent_complete, project_id)
+        VALUES (?1, ?2, ?3, ?4)
+        "#,
+        selected_task.id,
+        date,
+        percent,
+        project_id
+    )
+    .execute(pool)
+    .await?;
+
+    println!(
+        "📈 #{} - {} is {} % complete (was {} %)",
+        selected_task.id, selected_task.name, percent, selected_task.percent_complete
+    );
+    Ok(())
+}
+
+pub async fn import(
+    pool: &SqlitePool,
+    project_id: i64,
+    ms_project_file: String,
+    database_file: String,
+) -> anyhow::Result<()> {
     println!("Init {} {}", ms_project_file, database_file);
-    let tasks = load_from_csv(&ms_project_file).expect(&*format!(
-        "Failed to load tasks from CSV file {}",
-        ms_project_file
-    ));
+    let (tasks, problems) = load_plan(&ms_project_file)
+        .map_err(|e| anyhow!("Failed to load tasks from {}: {}", ms_project_file, e))?;
+    validate::check_import(&ms_project_file, &tasks, problems)?;
+
+    let mut tx = pool.begin().await?;
     for task in &tasks {
-        let _inserted_id = insert_task(&pool, &task)
-            .await
-            .expect("Failed to insert task");
+        insert_task(&mut tx, project_id, task).await?;
     }
+    // Links can only refer to tasks that are already stored
+    for task in &tasks {
+        dependency::save(&mut tx, project_id, task.id as i64, &task.links()).await?;
+    }
+    tx.commit().await?;
     println!("✨Imported {} tasks", tasks.len());
+    import_plan_calendar(pool, project_id, &ms_project_file).await?;
+    Ok(())
+}
+
+// Load tasks from a MS Project export, picking the format from the file extension. Tasks that
+// cannot be read are left out and reported as problems.
+fn load_plan(
+    ms_project_file: &str,
+) -> Result<(Vec<MsProjectTask>, Vec<validate::Problem>), Box<dyn std::error::Error>> {
+    if is_mspdi(ms_project_file) {
+        mspdi::load_from_xml(ms_project_file)
+    } else {
+        load_from_csv(ms_project_file)
+    }
+}
+
+fn is_mspdi(ms_project_file: &str) -> bool {
+    Path::new(ms_project_file)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("xml"))
+}
+
+// Take over the working weekdays and days off of the plan, only MS Project XML files carry them
+async fn import_plan_calendar(
+    pool: &SqlitePool,
+    project_id: i64,
+    ms_project_file: &str,
+) -> anyhow::Result<()> {
+    if !is_mspdi(ms_project_file) {
+        return Ok(());
+    }
+    let plan_calendar = mspdi::load_calendar(ms_project_file).map_err(|e| {
+        anyhow!(
+            "Failed to load the calendar from {}: {}",
+            ms_project_file,
+            e
+        )
+    })?;
+    if let Some(plan_calendar) = plan_calendar {
+        calendar::import_plan_calendar(pool, project_id, &plan_calendar).await?;
+    }
+    Ok(())
 }
 
 #[derive(Debug, Deserialize)]
@@ -280,7 +638,18 @@ struct MsProjectTask {
     #[serde(rename = "Resource_Names")]
     resource_names: String,
     #[serde(rename = "PDEx_Criticality")]
-    pdex_criticality: i32,
+    pdex_criticality: Option<i32>,
+    // Where the task is defined in the imported file
+    #[serde(skip)]
+    line: Option<u64>,
+}
+
+impl MsProjectTask {
+    // Links of the task, plans are validated before they are stored so unreadable links are
+    // left out
+    fn links(&self) -> Vec<dependency::Link> {
+        dependency::parse_links(&self.predecessors).unwrap_or_default()
+    }
 }
 
 //Parsing the total_slack field from a string like "30 days" into an integer.
@@ -291,9 +660,8 @@ where
     let s = String::deserialize(deserializer)?;
     s.split_whitespace()
         .next()
-        .unwrap()
-        .parse::<i32>()
-        .map_err(D::Error::custom)
+        .and_then(|days| days.parse::<i32>().ok())
+        .ok_or_else(|| D::Error::custom(format!("\"{}\" is not a number of days", s)))
 }
 
 fn parse_date<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
@@ -301,42 +669,77 @@ where
     D: serde::Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    NaiveDate::parse_from_str(&s, "%a %m/%d/%y").map_err(D::Error::custom)
+    NaiveDate::parse_from_str(&s, "%a %m/%d/%y")
+        .map_err(|e| D::Error::custom(format!("\"{}\" is not a date like Mon 8/7/23 ({})", s, e)))
 }
 
-async fn insert_task(pool: &SqlitePool, task: &MsProjectTask) -> anyhow::Result<i64> {
-    let mut conn = pool.acquire().await?;
+async fn insert_task(
+    conn: &mut SqliteConnection,
+    project_id: i64,
+    task: &MsProjectTask,
+) -> anyhow::Result<i64> {
     let start_date = task.start_date.format("%Y-%m-%d").to_string();
     let finish_date = task.finish_date.format("%Y-%m-%d").to_string();
 
     // Insert the task, then obtain the ID of this row
     let id = sqlx::query!(
     r#"
-    INSERT INTO tasks (id, name, duration, predecessors, start_date, finish_date, total_slack, resource_names, pdex_criticality)
+    INSERT INTO tasks (id, name, duration, start_date, finish_date, total_slack, resource_names, pdex_criticality, project_id)
     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
     "#,
-    task.id, task.name, task.duration, task.predecessors, start_date, finish_date, task.total_slack, task.resource_names, task.pdex_criticality
+    task.id, task.name, task.duration, start_date, finish_date, task.total_slack, task.resource_names, task.pdex_criticality, project_id
 )
-        .execute(&mut *conn)
+        .execute(conn)
         .await?
         .last_insert_rowid();
     Ok(id)
 }
 
 // Function that retrieves Tasks from CSV file
-fn load_from_csv(path: &str) -> Result<Vec<MsProjectTask>, Box<dyn std::error::Error>> {
+fn load_from_csv(
+    path: &str,
+) -> Result<(Vec<MsProjectTask>, Vec<validate::Problem>), Box<dyn std::error::Error>> {
     let mut reader = Reader::from_path(path)?;
+    let headers = reader.headers()?.clone();
     let mut tasks: Vec<MsProjectTask> = Vec::new();
+    let mut problems = vec![];
 
-    for record in reader.deserialize() {
-        let record: MsProjectTask = record?;
-        tasks.push(record);
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map(|position| position.line());
+                problems.push(validate::Problem::error(line, None, e.to_string()));
+                continue;
+            }
+        };
+        let line = record.position().map(|position| position.line());
+        match record.deserialize::<MsProjectTask>(Some(&headers)) {
+            Ok(mut task) => {
+                task.line = line;
+                tasks.push(task);
+            }
+            Err(e) => {
+                // Name the column instead of the position csv reports
+                let message = match e.kind() {
+                    csv::ErrorKind::Deserialize { err, .. } => {
+                        match err.field().and_then(|field| headers.get(field as usize)) {
+                            Some(column) => format!("Cannot read {}, {}", column, err.kind()),
+                            None => format!("Cannot read task, {}", err.kind()),
+                        }
+                    }
+                    _ => e.to_string(),
+                };
+                let task_id = record.get(0).and_then(|id| id.trim().parse().ok());
+                problems.push(validate::Problem::error(line, task_id, message));
+            }
+        }
     }
 
-    Ok(tasks)
+    Ok((tasks, problems))
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, ValueEnum)]
 pub enum TaskStatus {
     Pending,
     Completed,
@@ -345,17 +748,29 @@ pub enum TaskStatus {
     Unassigned,
 }
 
-pub(crate) async fn complete_tasks(pool: &Pool<Sqlite>) -> anyhow::Result<()> {
+pub(crate) async fn complete_tasks(
+    pool: &Pool<Sqlite>,
+    project_id: i64,
+    config: &Config,
+    task_ids: &[i64],
+    date: Option<NaiveDate>,
+) -> anyhow::Result<()> {
     // Insert the task, then obtain the ID of this row
     let tasks = sqlx::query!(
         r#"
-        SELECT t.id, t.name, td.assignee as assignee FROM tasks t, task_data td WHERE t.id = td.task_id AND td.finished_at IS NULL
-        "#
+        SELECT t.id, t.name, tm.name as "assignee?"
+        FROM tasks t
+                 JOIN task_data td ON td.project_id = t.project_id AND td.task_id = t.id
+                 LEFT JOIN team tm ON tm.id = td.member_id
+        WHERE t.project_id = ?1
+          AND td.finished_at IS NULL
+        "#,
+        project_id
     )
     .fetch_all(pool)
     .await?;
 
-    let options = tasks
+    let options: Vec<ListOption<String>> = tasks
         .iter()
         .map(|task| {
             ListOption::new(
@@ -369,36 +784,63 @@ pub(crate) async fn complete_tasks(pool: &Pool<Sqlite>) -> anyhow::Result<()> {
         })
         .collect();
 
-    let tasks_to_complete = MultiSelect::new("Select tasks to complete:", options).prompt();
-
-    match tasks_to_complete {
-        Ok(..) => {
-            let tasks = tasks_to_complete.expect("Error in selection");
-            for task in tasks {
-                let task_id = task.index as i32;
-                sqlx::query!(
-                    r#"
-                    UPDATE task_data SET finished_at = CURRENT_TIMESTAMP WHERE task_id = ?1
-                    "#,
-                    task_id
-                )
-                .execute(pool)
-                .await?;
-                println!("✨Completed task #{} - {}", task_id, task.value);
+    let tasks_to_complete = if task_ids.is_empty() {
+        match MultiSelect::new("Select tasks to complete:", options).prompt() {
+            Ok(tasks) => tasks,
+            Err(_) => {
+                println!("Error in selection");
+                return Ok(());
             }
         }
-        Err(_) => println!("Error in selection"),
+    } else {
+        let mut selected = vec![];
+        for id in task_ids {
+            let option = options
+                .iter()
+                .find(|option| option.index == *id as usize)
+                .ok_or_else(|| anyhow!("Task #{} is not assigned or already completed", id))?;
+            selected.push(option.clone());
+        }
+        selected
+    };
+
+    // Without a date the task is completed now
+    let finished_at = date.map(|date| format!("{} 00:00:00", date.format("%Y-%m-%d")));
+    for task in tasks_to_complete {
+        let task_id = task.index as i32;
+        sqlx::query!(
+            r#"
+            UPDATE task_data SET finished_at = COALESCE(?2, CURRENT_TIMESTAMP)
+            WHERE project_id = ?3 AND task_id = ?1
+            "#,
+            task_id,
+            finished_at,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        println!("✨Completed task #{} - {}", task_id, task.value);
     }
+    schedule::update(pool, project_id, config).await?;
     Ok(())
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ListFormat {
+    Table,
+    Json,
+    Csv,
+    Markdown,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[allow(dead_code)]
 struct Task {
     id: i64,
     name: String,
     duration: i64,
     slack: i64,
-    predecessors: Vec<i64>,
+    predecessors: Vec<dependency::Link>,
     start_date: NaiveDate,
     finish_date: NaiveDate,
     resource_names: Vec<String>,
@@ -406,56 +848,244 @@ struct Task {
     assignee: Option<String>,
     finished_at: Option<NaiveDate>,
     finished: bool,
+    percent_complete: i64,
 }
 
-pub(crate) async fn assign_tasks(pool: Pool<Sqlite>) -> anyhow::Result<()> {
-    let tasks_to_assign = select_tasks(
-        get_tasks(pool.clone(), TaskStatus::Unassigned).await?,
-        "Select tasks to assign:",
-        20,
-    )
-    .expect("Error when selecting tasks to assign");
+// Task with its baseline comparison, as printed by `list --output json`
+#[derive(Serialize)]
+struct ListedTask<'a> {
+    #[serde(flatten)]
+    task: &'a Task,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    baseline_finish_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finish_variance: Option<i64>,
+}
 
-    // Get assignees from env variable comma separated
-    let team_members = std::env::var("PROJECT_MANAGER_TEAM_MEMBERS")
-        .expect("PROJECT_MANAGER_TEAM_MEMBERS not set")
-        .split(",")
-        .map(|s| titlecase(s.trim()))
-        .collect::<Vec<String>>();
+pub(crate) async fn assign_tasks(
+    pool: Pool<Sqlite>,
+    project_id: i64,
+    config: &Config,
+    task_ids: &[i64],
+    assignee: Option<String>,
+    force: bool,
+) -> anyhow::Result<()> {
+    let unassigned_tasks = get_tasks(pool.clone(), project_id, TaskStatus::Unassigned).await?;
+    let tasks_to_assign = if task_ids.is_empty() {
+        select_tasks(
+            unassigned_tasks,
+            "Select tasks to assign:",
+            20,
+            &config.dates,
+        )
+        .expect("Error when selecting tasks to assign")
+    } else {
+        let mut selected = vec![];
+        for id in task_ids {
+            let task = unassigned_tasks
+                .iter()
+                .find(|task| task.id == *id)
+                .ok_or_else(|| anyhow!("Task #{} does not exist or is already assigned", id))?;
+            selected.push(task.clone());
+        }
+        selected
+    };
 
-    let ans: InquireResult<String> = Select::new("Select assignee: ", team_members).prompt();
-    let assignee = match ans {
-        Ok(assignee) => Some(assignee),
-        Err(_) => None,
-    }
-    .expect("No assignee selected");
+    let interactive = assignee.is_none();
+    let member = match assignee {
+        Some(assignee) => team::find(&pool, &assignee).await?,
+        None => {
+            let members = team::members(&pool).await?;
+            if members.is_empty() {
+                bail!("No team members yet, add them with `team add`");
+            }
+            // Suggest the members mapped to the planned resources first, then anyone with
+            // the planned role, everyone else only when nobody has it
+            let mappings = team::mappings(&pool, project_id).await?;
+            let mapped: Vec<i64> = tasks_to_assign
+                .iter()
+                .flat_map(|task| task.resource_names.iter())
+                .filter_map(|resource| mappings.get(&resource.to_uppercase()).copied())
+                .collect();
+            let mut suggested: Vec<team::Member> = members
+                .iter()
+                .filter(|member| {
+                    tasks_to_assign
+                        .iter()
+                        .all(|task| team::has_role_for(member, &task.resource_names))
+                })
+                .cloned()
+                .collect();
+            suggested.sort_by_key(|member| !mapped.contains(&member.id));
+            let members = if suggested.is_empty() {
+                println!("⚠️ Nobody in the team has the planned role, showing everyone");
+                members
+            } else {
+                suggested
+            };
+            let options: Vec<ListOption<String>> = members
+                .iter()
+                .enumerate()
+                .map(|(index, member)| {
+                    ListOption::new(
+                        index,
+                        format!(
+                            "{} ({})",
+                            member.name,
+                            member.role.as_deref().unwrap_or("no role")
+                        ),
+                    )
+                })
+                .collect();
+            let ans: InquireResult<ListOption<String>> =
+                Select::new("Select assignee: ", options).prompt();
+            members[ans.expect("No assignee selected").index].clone()
+        }
+    };
 
     for task in tasks_to_assign {
-        // Insert the task, then obtain the ID of this row
-        let id = sqlx::query!(
-            r#"
-        INSERT INTO task_data (assignee, task_id) VALUES (LOWER(?1), ?2)
-        "#,
-            assignee,
-            task.id
-        )
-        .execute(&pool)
+        // Assignments are validated against the roles planned in MS Project
+        if !team::has_role_for(&member, &task.resource_names) {
+            let warning = format!(
+                "⚠️ {} is {} but #{} - {} is planned for {}",
+                member.name,
+                member.role.as_deref().unwrap_or("--"),
+                task.id,
+                task.name,
+                task.resource_names.join(",")
+            );
+            if interactive {
+                let confirm = prompt_default(format!("{}, assign anyway?", warning), false);
+                if confirm.is_err() || !confirm.unwrap() {
+                    println!("⏭️ Skipped task #{}", task.id);
+                    continue;
+                }
+            } else if force {
+                println!("{}", warning);
+            } else {
+                bail!("{}, use --force to assign anyway", warning);
+            }
+        }
+
+        assign(&pool, project_id, &task, &member).await?;
+    }
+
+    allocation::warn_overallocated(&pool, project_id, &member.name).await?;
+    Ok(())
+}
+
+// Map a planned MS Project resource (e.g. DEV3) to a team member and assign them all
+// unassigned tasks planned for it
+pub(crate) async fn map_resource(
+    pool: &SqlitePool,
+    project_id: i64,
+    resource: &str,
+    member: &str,
+    yes: bool,
+    force: bool,
+) -> anyhow::Result<()> {
+    let resource = resource.trim().to_uppercase();
+    let member = team::find(pool, member).await?;
+    let resources = vec![resource.clone()];
+    if !team::has_role_for(&member, &resources) {
+        let warning = format!(
+            "⚠️ {} is {} but {} is a different role",
+            member.name,
+            member.role.as_deref().unwrap_or("--"),
+            resource
+        );
+        if !force {
+            bail!("{}, use --force to map anyway", warning);
+        }
+        println!("{}", warning);
+    }
+
+    let tasks: Vec<Task> = get_tasks(pool.clone(), project_id, TaskStatus::Unassigned)
         .await?
-        .last_insert_rowid();
+        .into_iter()
+        .filter(|task| {
+            task.resource_names
+                .iter()
+                .any(|name| name.trim().eq_ignore_ascii_case(&resource))
+        })
+        .collect();
 
+    for task in &tasks {
         println!(
-            "✨Assigned task #{} - {} to {} (rowid: {})",
+            "  #{} - {} ({})",
             task.id,
             task.name,
-            assignee,
-            id.to_string()
+            task.resource_names.join(",")
+        );
+    }
+    if !yes {
+        let confirm = prompt_default(
+            format!(
+                "🔗 Map {} → {} and assign {} tasks?",
+                resource,
+                member.name,
+                tasks.len()
+            ),
+            false,
         );
+        if confirm.is_err() || !confirm.unwrap() {
+            println!("👋 Nothing mapped, bye!");
+            return Ok(());
+        }
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO resource_mappings (project_id, resource_name, member_id) VALUES (?1, ?2, ?3)
+        ON CONFLICT (project_id, resource_name) DO UPDATE SET member_id = excluded.member_id
+        "#,
+        project_id,
+        resource,
+        member.id
+    )
+    .execute(pool)
+    .await?;
+
+    for task in &tasks {
+        assign(pool, project_id, task, &member).await?;
     }
+    println!(
+        "🔗 Mapped {} → {}, assigned {} tasks",
+        resource,
+        member.name,
+        tasks.len()
+    );
+    allocation::warn_overallocated(pool, project_id, &member.name).await?;
+    Ok(())
+}
+
+async fn assign(
+    pool: &SqlitePool,
+    project_id: i64,
+    task: &Task,
+    member: &team::Member,
+) -> anyhow::Result<()> {
+    // Insert the task, then obtain the ID of this row
+    let id = sqlx::query!(
+        r#"
+        INSERT INTO task_data (member_id, task_id, project_id) VALUES (?1, ?2, ?3)
+        "#,
+        member.id,
+        task.id,
+        project_id
+    )
+    .execute(pool)
+    .await?
+    .last_insert_rowid();
 
+    println!(
+        "✨Assigned task #{} - {} to {} (rowid: {})",
+        task.id, task.name, member.name, id
+    );
     Ok(())
 }
 
-fn select_task(tasks: Vec<Task>, prompt: &str) -> Option<Task> {
+fn select_task(tasks: Vec<Task>, prompt: &str, dates: &DateFormats) -> Option<Task> {
     let options: Vec<ListOption<String>> = tasks
         .iter()
         .map(|task| {
@@ -465,7 +1095,7 @@ fn select_task(tasks: Vec<Task>, prompt: &str) -> Option<Task> {
                     "#{} - {} (start: {})",
                     task.id,
                     task.name.as_str(),
-                    task.start_date.format("%d.%m.%y")
+                    task.start_date.format(&dates.short)
                 ),
             )
         })
@@ -485,7 +1115,12 @@ fn select_task(tasks: Vec<Task>, prompt: &str) -> Option<Task> {
     }
 }
 
-fn select_tasks(tasks: Vec<Task>, prompt: &str, page_size: usize) -> Option<Vec<Task>> {
+fn select_tasks(
+    tasks: Vec<Task>,
+    prompt: &str,
+    page_size: usize,
+    dates: &DateFormats,
+) -> Option<Vec<Task>> {
     let options: Vec<ListOption<String>> = tasks
         .iter()
         .map(|task| {
@@ -495,7 +1130,7 @@ fn select_tasks(tasks: Vec<Task>, prompt: &str, page_size: usize) -> Option<Vec<
                     "#{} - {} (start: {})",
                     task.id,
                     task.name.as_str(),
-                    task.start_date.format("%d.%m.%y")
+                    task.start_date.format(&dates.short)
                 ),
             )
         })
@@ -522,6 +1157,320 @@ fn select_tasks(tasks: Vec<Task>, prompt: &str, page_size: usize) -> Option<Vec<
     }
 }
 
-fn dfmt(date: NaiveDate) -> String {
-    date.format("%a %d.%m.%y").to_string()
+fn dfmt(date: NaiveDate, dates: &DateFormats) -> String {
+    date.format(&dates.display).to_string()
+}
+
+// Tasks with little slack are the ones to keep an eye on
+fn slack_color(slack: i64, thresholds: &SlackThresholds) -> Color {
+    match risk::FloatBucket::of(slack, thresholds) {
+        risk::FloatBucket::Critical | risk::FloatBucket::Red => Color::Red,
+        risk::FloatBucket::Yellow => Color::Yellow,
+        risk::FloatBucket::Green => Color::Green,
+    }
+}
+
+// The default project every migrated database starts with
+#[cfg(test)]
+pub(crate) const TEST_PROJECT: i64 = 1;
+
+// Fresh in-memory database. Every connection to an in-memory database opens a new one, so the
+// pool keeps a single one.
+#[cfg(test)]
+pub(crate) async fn test_pool() -> Pool<Sqlite> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::migrate!().run(&pool).await.unwrap();
+    pool
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::calendar::Calendar;
+    use crate::project::dependency::{Link, LinkType};
+
+    fn listed_tasks() -> Vec<Task> {
+        let date = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+        vec![
+            Task {
+                id: 2,
+                name: "Design | review".to_string(),
+                duration: 5,
+                slack: 0,
+                predecessors: vec![Link::new(1, LinkType::FinishToStart, 0)],
+                start_date: date(19),
+                finish_date: date(23),
+                resource_names: vec!["ARC".to_string(), "UX".to_string()],
+                pdex_criticality: 0,
+                assignee: Some("dana".to_string()),
+                finished_at: Some(date(22)),
+                finished: true,
+                percent_complete: 100,
+            },
+            Task {
+                id: 3,
+                name: "Build".to_string(),
+                duration: 3,
+                slack: 2,
+                predecessors: vec![
+                    Link::new(1, LinkType::FinishToStart, 0),
+                    Link::new(2, LinkType::StartToStart, 1),
+                ],
+                start_date: date(26),
+                finish_date: date(28),
+                resource_names: vec!["DEV".to_string()],
+                pdex_criticality: 0,
+                assignee: None,
+                finished_at: None,
+                finished: false,
+                percent_complete: 40,
+            },
+        ]
+    }
+
+    fn baseline() -> Option<HashMap<i64, NaiveDate>> {
+        Some(HashMap::from([(
+            3,
+            NaiveDate::from_ymd_opt(2026, 10, 23).unwrap(),
+        )]))
+    }
+
+    #[test]
+    fn lists_tasks_as_csv() {
+        assert_eq!(
+            list_csv(&listed_tasks(), &None, &Calendar::standard(&[])).unwrap(),
+            "#,Assignee,Task,Estimated Duration,Slack,Done,Planned Start Date,Planned Finish Date,Actual Finish Date,Predecessors\n\
+             2,dana,Design | review,5,0,100,2026-10-19,2026-10-23,2026-10-22,1\n\
+             3,,Build,3,2,40,2026-10-26,2026-10-28,,\"1,2SS+1 day\"\n"
+        );
+    }
+
+    #[test]
+    fn csv_compares_with_the_baseline() {
+        let csv = list_csv(&listed_tasks(), &baseline(), &Calendar::standard(&[])).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert!(lines[0].ends_with(",Predecessors,Baseline Finish Date,Finish Variance"));
+        assert!(lines[1].ends_with(",2026-10-22,1,,"));
+        assert!(lines[2].ends_with(",\"1,2SS+1 day\",2026-10-23,3"));
+    }
+
+    #[test]
+    fn lists_tasks_as_markdown() {
+        assert_eq!(
+            list_markdown(
+                &listed_tasks(),
+                &baseline(),
+                &Calendar::standard(&[]),
+                &DateFormats::default()
+            ),
+            "| # | Assignee | Task | Estimated Duration | Slack | Done | Planned Start Date | Planned Finish Date | Actual Finish Date | Predecessors | Baseline Finish Date | Finish Variance |\n\
+             |---|---|---|---|---|---|---|---|---|---|---|---|\n\
+             | 2 | dana | Design \\| review | 5 | 0 | 100 | Mon 19.10.26 | Fri 23.10.26 | Thu 22.10.26 | 1 |  |  |\n\
+             | 3 |  | Build | 3 | 2 | 40 | Mon 26.10.26 | Wed 28.10.26 |  | 1,2SS+1 day | Fri 23.10.26 | 3 |\n"
+        );
+    }
+
+    #[test]
+    fn markdown_dates_follow_the_configured_format() {
+        let dates = DateFormats {
+            display: "%d/%m/%Y".to_string(),
+            ..Default::default()
+        };
+        let markdown = list_markdown(&listed_tasks(), &None, &Calendar::standard(&[]), &dates);
+        assert!(
+            markdown.contains("| 19/10/2026 | 23/10/2026 |"),
+            "{}",
+            markdown
+        );
+    }
+
+    #[test]
+    fn lists_tasks_as_json() {
+        let json: serde_json::Value = serde_json::from_str(
+            &list_json(&listed_tasks(), &baseline(), &Calendar::standard(&[])).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json[0]["id"], 2);
+        assert_eq!(json[0]["finished_at"], "2026-10-22");
+        assert_eq!(json[0]["resource_names"], serde_json::json!(["ARC", "UX"]));
+        assert!(json[0].get("baseline_finish_date").is_none());
+        assert_eq!(
+            json[1]["predecessors"][1],
+            serde_json::json!({"predecessor": 2, "type": "SS", "lag": 1})
+        );
+        assert_eq!(json[1]["percent_complete"], 40);
+        assert_eq!(json[1]["assignee"], serde_json::Value::Null);
+        assert_eq!(json[1]["baseline_finish_date"], "2026-10-23");
+        assert_eq!(json[1]["finish_variance"], 3);
+    }
+
+    #[tokio::test]
+    async fn logs_work_without_prompts() {
+        let pool = test_pool().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack,
+                               resource_names)
+            VALUES (1, 1, 'Start', 0, '2026-10-19', '2026-10-19', 0, ''),
+                   (1, 2, 'Design', 5, '2026-10-19', '2026-10-23', 0, 'DEV');
+            INSERT INTO dependencies (project_id, task_id, predecessor_id) VALUES (1, 2, 1);
+            INSERT INTO team (id, name) VALUES (1, 'dana');
+            INSERT INTO task_data (project_id, task_id, member_id) VALUES (1, 2, 1);
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2026, 10, 20).unwrap();
+        log_work(
+            &pool,
+            TEST_PROJECT,
+            &Config::default(),
+            Some(2),
+            Some(date),
+            Some(2),
+            None,
+        )
+        .await
+        .unwrap();
+        log_work(
+            &pool,
+            TEST_PROJECT,
+            &Config::default(),
+            Some(2),
+            Some(date),
+            Some(1),
+            Some("Bob".to_string()),
+        )
+        .await
+        .unwrap();
+
+        let logged =
+            sqlx::query!(r#"SELECT task_id, date, duration, person, member_id FROM timesheet"#)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(logged.len(), 2);
+        assert_eq!(logged[0].task_id, Some(2));
+        assert_eq!(logged[0].date.as_deref(), Some("2026-10-20"));
+        assert_eq!(logged[0].duration, Some(2));
+        // The assignee logs the work unless someone else is named
+        assert_eq!(logged[0].person.as_deref(), Some("dana"));
+        assert_eq!(logged[1].person.as_deref(), Some("bob"));
+        // Only team members are linked to the work they log
+        assert_eq!(logged[0].member_id, Some(1));
+        assert_eq!(logged[1].member_id, None);
+    }
+
+    #[tokio::test]
+    async fn refuses_logging_on_unassigned_tasks() {
+        let pool = test_pool().await;
+        let date = NaiveDate::from_ymd_opt(2026, 10, 20).unwrap();
+
+        let error = log_work(
+            &pool,
+            TEST_PROJECT,
+            &Config::default(),
+            Some(1),
+            Some(date),
+            Some(2),
+            None,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Task #1 is not assigned or already completed"
+        );
+    }
+
+    #[tokio::test]
+    async fn refuses_logging_no_work() {
+        let pool = test_pool().await;
+        for days in [0, -3] {
+            let error = log_work(
+                &pool,
+                TEST_PROJECT,
+                &Config::default(),
+                Some(1),
+                None,
+                Some(days),
+                None,
+            )
+            .await
+            .err()
+            .unwrap();
+            assert_eq!(error.to_string(), "Logged work must be at least one day");
+        }
+    }
+
+    #[tokio::test]
+    async fn maps_a_resource_and_assigns_its_tasks() {
+        let pool = test_pool().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack,
+                               resource_names)
+            VALUES (1, 1, 'Start', 0, '2026-10-19', '2026-10-19', 0, ''),
+                   (1, 2, 'Design', 5, '2026-10-19', '2026-10-23', 0, 'DEV1'),
+                   (1, 3, 'Review', 2, '2026-10-26', '2026-10-27', 0, 'ARC1,DEV1'),
+                   (1, 4, 'Build', 5, '2026-10-26', '2026-10-30', 0, 'DEV2'),
+                   (1, 5, 'Fix', 1, '2026-10-26', '2026-10-26', 4, 'DEV1');
+            INSERT INTO dependencies (project_id, task_id, predecessor_id)
+            VALUES (1, 2, 1),
+                   (1, 3, 2),
+                   (1, 4, 2),
+                   (1, 5, 2);
+            INSERT INTO team (id, name, role) VALUES (1, 'dana', 'DEV'), (2, 'bob', 'ARC');
+            INSERT INTO task_data (project_id, task_id, member_id) VALUES (1, 5, 2);
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let error = map_resource(&pool, TEST_PROJECT, "dev1", "bob", true, false)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "⚠️ bob is ARC but DEV1 is a different role, use --force to map anyway"
+        );
+
+        map_resource(&pool, TEST_PROJECT, "dev1", "dana", true, false)
+            .await
+            .unwrap();
+        let assigned = sqlx::query!(
+            r#"SELECT task_id as "task_id!", member_id as "member_id!" FROM task_data ORDER BY task_id"#
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let assigned: Vec<(i64, i64)> = assigned
+            .iter()
+            .map(|row| (row.task_id, row.member_id))
+            .collect();
+        // Tasks already assigned to someone else stay with them
+        assert_eq!(assigned, vec![(2, 1), (3, 1), (5, 2)]);
+        assert_eq!(
+            team::mappings(&pool, TEST_PROJECT).await.unwrap(),
+            HashMap::from([("DEV1".to_string(), 1)])
+        );
+
+        // Mapping the resource again moves it to the other member
+        map_resource(&pool, TEST_PROJECT, "DEV1", "bob", true, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            team::mappings(&pool, TEST_PROJECT).await.unwrap(),
+            HashMap::from([("DEV1".to_string(), 2)])
+        );
+    }
 }
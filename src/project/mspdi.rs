@@ -0,0 +1,642 @@
+use std::collections::HashMap;
+use std::fs;
+
+use chrono::{NaiveDate, NaiveDateTime, Weekday};
+use serde::Deserialize;
+
+use crate::project::dependency::{Link, LinkType};
+use crate::project::validate::Problem;
+use crate::project::MsProjectTask;
+
+// MS Project stores work time in minutes, slack and lag in tenths of minutes
+const DEFAULT_MINUTES_PER_DAY: i64 = 480;
+// Elapsed lag runs around the clock and through weekends
+const MINUTES_PER_ELAPSED_DAY: f64 = 1440.0;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Project {
+    minutes_per_day: Option<i64>,
+    #[serde(rename = "CalendarUID")]
+    calendar_uid: Option<i64>,
+    calendars: Option<Calendars>,
+    tasks: Option<Tasks>,
+    resources: Option<Resources>,
+    assignments: Option<Assignments>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Calendars {
+    #[serde(rename = "Calendar", default)]
+    calendars: Vec<Calendar>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Calendar {
+    #[serde(rename = "UID")]
+    uid: i64,
+    #[serde(rename = "BaseCalendarUID")]
+    base_calendar_uid: Option<i64>,
+    week_days: Option<WeekDays>,
+    exceptions: Option<Exceptions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WeekDays {
+    #[serde(rename = "WeekDay", default)]
+    week_days: Vec<WeekDay>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct WeekDay {
+    // 1 = Sunday .. 7 = Saturday, 0 is used for exceptions
+    day_type: i64,
+    day_working: i64,
+    working_times: Option<WorkingTimes>,
+    time_period: Option<TimePeriod>,
+}
+
+// Exceptions of MS Project 2007 and later, older files list them as week days of type 0
+#[derive(Debug, Deserialize)]
+struct Exceptions {
+    #[serde(rename = "Exception", default)]
+    exceptions: Vec<Exception>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Exception {
+    name: Option<String>,
+    day_working: Option<i64>,
+    time_period: Option<TimePeriod>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TimePeriod {
+    from_date: String,
+    to_date: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkingTimes {
+    #[serde(rename = "WorkingTime", default)]
+    working_times: Vec<WorkingTime>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct WorkingTime {
+    from_time: String,
+    to_time: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tasks {
+    #[serde(rename = "Task", default)]
+    tasks: Vec<Task>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Task {
+    #[serde(rename = "UID")]
+    uid: i64,
+    #[serde(rename = "ID")]
+    id: Option<i64>,
+    name: Option<String>,
+    start: Option<String>,
+    finish: Option<String>,
+    duration: Option<String>,
+    summary: Option<i64>,
+    total_slack: Option<i64>,
+    #[serde(rename = "PredecessorLink", default)]
+    predecessor_links: Vec<PredecessorLink>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PredecessorLink {
+    #[serde(rename = "PredecessorUID")]
+    predecessor_uid: i64,
+    // 0 = FF, 1 = FS, 2 = SF, 3 = SS
+    #[serde(rename = "Type")]
+    link_type: Option<i64>,
+    link_lag: Option<i64>,
+    // 7 = days, 8 = elapsed days, 19 = percent of the predecessor, ...
+    lag_format: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Resources {
+    #[serde(rename = "Resource", default)]
+    resources: Vec<Resource>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Resource {
+    #[serde(rename = "UID")]
+    uid: i64,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Assignments {
+    #[serde(rename = "Assignment", default)]
+    assignments: Vec<Assignment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Assignment {
+    #[serde(rename = "TaskUID")]
+    task_uid: i64,
+    #[serde(rename = "ResourceUID")]
+    resource_uid: i64,
+}
+
+// Function that retrieves Tasks from an MS Project XML (MSPDI) file
+pub(crate) fn load_from_xml(
+    path: &str,
+) -> Result<(Vec<MsProjectTask>, Vec<Problem>), Box<dyn std::error::Error>> {
+    let xml = fs::read_to_string(path)?;
+    let project: Project = quick_xml::de::from_str(&xml)?;
+    let lines = task_lines(&xml);
+
+    let minutes_per_day = project
+        .minutes_per_day
+        .filter(|minutes| *minutes > 0)
+        .or_else(|| calendar_minutes_per_day(&project))
+        .unwrap_or(DEFAULT_MINUTES_PER_DAY);
+    let workdays_per_week = plan_calendar(&project)
+        .map(|calendar| calendar.weekdays.len())
+        .unwrap_or(5);
+
+    let tasks: Vec<(Task, Option<u64>)> = project
+        .tasks
+        .map(|tasks| tasks.tasks)
+        .unwrap_or_default()
+        .into_iter()
+        .zip(lines.into_iter().map(Some).chain(std::iter::repeat(None)))
+        .filter(|(task, _)| task.uid != 0 && task.summary != Some(1) && task.name.is_some())
+        .collect();
+
+    // Links and assignments refer to tasks by UID while the task table is keyed on ID
+    let ids: HashMap<i64, i64> = tasks
+        .iter()
+        .map(|(task, _)| (task.uid, task.id.unwrap_or(task.uid)))
+        .collect();
+    let durations: HashMap<i64, Option<i32>> = tasks
+        .iter()
+        .map(|(task, _)| (task.uid, duration_days(task, minutes_per_day)))
+        .collect();
+
+    let resources: HashMap<i64, String> = project
+        .resources
+        .map(|resources| resources.resources)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|resource| resource.name.map(|name| (resource.uid, name)))
+        .collect();
+
+    let mut resource_names: HashMap<i64, Vec<String>> = HashMap::new();
+    for assignment in project
+        .assignments
+        .map(|assignments| assignments.assignments)
+        .unwrap_or_default()
+    {
+        if let Some(name) = resources.get(&assignment.resource_uid) {
+            resource_names
+                .entry(assignment.task_uid)
+                .or_default()
+                .push(name.clone());
+        }
+    }
+
+    let mut ms_project_tasks = vec![];
+    let mut problems = vec![];
+    for (task, line) in tasks {
+        let id = ids[&task.uid];
+        let mut predecessors = vec![];
+        for link in &task.predecessor_links {
+            match ids.get(&link.predecessor_uid) {
+                Some(predecessor) => {
+                    let predecessor_days = durations[&link.predecessor_uid].unwrap_or_default();
+                    let lag = lag_days(link, minutes_per_day, workdays_per_week, predecessor_days);
+                    predecessors.push(format_link(*predecessor, link, lag))
+                }
+                None => problems.push(Problem::error(
+                    line,
+                    Some(id),
+                    format!(
+                        "Predecessor with UID {} does not exist",
+                        link.predecessor_uid
+                    ),
+                )),
+            }
+        }
+
+        let duration = durations[&task.uid];
+        let start_date = parse_date(task.start.as_deref());
+        let finish_date = parse_date(task.finish.as_deref());
+        let (Some(duration), Some(start_date), Some(finish_date)) =
+            (duration, start_date, finish_date)
+        else {
+            let field = if duration.is_none() {
+                "duration"
+            } else if start_date.is_none() {
+                "start date"
+            } else {
+                "finish date"
+            };
+            problems.push(Problem::error(
+                line,
+                Some(id),
+                format!("Invalid {} for task UID {}", field, task.uid),
+            ));
+            continue;
+        };
+
+        ms_project_tasks.push(MsProjectTask {
+            id: id as i32,
+            name: task.name.unwrap_or_default(),
+            duration,
+            predecessors: predecessors.join(","),
+            start_date,
+            finish_date,
+            total_slack: (task.total_slack.unwrap_or(0) as f64 / 10.0 / minutes_per_day as f64)
+                .round() as i32,
+            resource_names: resource_names
+                .remove(&task.uid)
+                .unwrap_or_default()
+                .join(","),
+            pdex_criticality: None,
+            line,
+        });
+    }
+
+    Ok((ms_project_tasks, problems))
+}
+
+// Working weekdays and days off of the project calendar of a plan
+#[derive(Debug, PartialEq)]
+pub(crate) struct PlanCalendar {
+    pub(crate) weekdays: Vec<Weekday>,
+    // First and last day off and the name of each exception without working time
+    pub(crate) days_off: Vec<(NaiveDate, NaiveDate, String)>,
+}
+
+// Project calendar of an MS Project XML (MSPDI) file, if it has one
+pub(crate) fn load_calendar(
+    path: &str,
+) -> Result<Option<PlanCalendar>, Box<dyn std::error::Error>> {
+    let xml = fs::read_to_string(path)?;
+    let project: Project = quick_xml::de::from_str(&xml)?;
+    Ok(plan_calendar(&project))
+}
+
+fn plan_calendar(project: &Project) -> Option<PlanCalendar> {
+    let calendar = project_calendar(project)?;
+    // Day types start on Sunday, the calendars here on Monday
+    let weekdays: Vec<Weekday> = (2..=7)
+        .chain([1])
+        .filter(|day_type| {
+            week_day(project, calendar, *day_type).is_some_and(|day| day.day_working == 1)
+        })
+        .filter_map(weekday)
+        .collect();
+    if weekdays.is_empty() {
+        return None;
+    }
+
+    // Exceptions with working time change the hours of a day, which the plan doesn't track
+    let old_exceptions = calendar
+        .week_days
+        .iter()
+        .flat_map(|week_days| &week_days.week_days)
+        .filter(|day| day.day_type == 0 && day.day_working == 0)
+        .filter_map(|day| Some((day.time_period.as_ref()?, String::new())));
+    let exceptions = calendar
+        .exceptions
+        .iter()
+        .flat_map(|exceptions| &exceptions.exceptions)
+        .filter(|exception| exception.day_working.unwrap_or(0) == 0)
+        .filter_map(|exception| {
+            Some((
+                exception.time_period.as_ref()?,
+                exception.name.clone().unwrap_or_default(),
+            ))
+        });
+    let days_off = old_exceptions
+        .chain(exceptions)
+        .filter_map(|(period, name)| {
+            let from = parse_date(Some(&period.from_date))?;
+            let to = parse_date(Some(&period.to_date))?;
+            (from <= to).then_some((from, to, name))
+        })
+        .collect();
+
+    Some(PlanCalendar { weekdays, days_off })
+}
+
+fn project_calendar(project: &Project) -> Option<&Calendar> {
+    project
+        .calendars
+        .as_ref()?
+        .calendars
+        .iter()
+        .find(|calendar| Some(calendar.uid) == project.calendar_uid)
+}
+
+// Week day of a calendar, derived calendars leave out the days they take from their base
+fn week_day<'a>(
+    project: &'a Project,
+    calendar: &'a Calendar,
+    day_type: i64,
+) -> Option<&'a WeekDay> {
+    let day = calendar
+        .week_days
+        .iter()
+        .flat_map(|week_days| &week_days.week_days)
+        .find(|day| day.day_type == day_type);
+    day.or_else(|| {
+        let base = project.calendars.as_ref()?.calendars.iter().find(|base| {
+            Some(base.uid) == calendar.base_calendar_uid && base.uid != calendar.uid
+        })?;
+        week_day(project, base, day_type)
+    })
+}
+
+fn weekday(day_type: i64) -> Option<Weekday> {
+    match day_type {
+        1 => Some(Weekday::Sun),
+        2 => Some(Weekday::Mon),
+        3 => Some(Weekday::Tue),
+        4 => Some(Weekday::Wed),
+        5 => Some(Weekday::Thu),
+        6 => Some(Weekday::Fri),
+        7 => Some(Weekday::Sat),
+        _ => None,
+    }
+}
+
+fn duration_days(task: &Task, minutes_per_day: i64) -> Option<i32> {
+    parse_duration(task.duration.as_deref().unwrap_or("PT0H0M0S"))
+        .map(|minutes| (minutes as f64 / minutes_per_day as f64).round() as i32)
+}
+
+// Line of each <Task> element in the task list, in document order
+fn task_lines(xml: &str) -> Vec<u64> {
+    let Some(start) = xml.find("<Tasks>") else {
+        return vec![];
+    };
+    let end = xml[start..]
+        .find("</Tasks>")
+        .map(|end| start + end)
+        .unwrap_or(xml.len());
+
+    let mut lines = vec![];
+    let mut line = xml[..start].matches('\n').count() as u64 + 1;
+    let mut position = start;
+    while let Some(found) = xml[position..end].find("<Task>") {
+        line += xml[position..position + found].matches('\n').count() as u64;
+        lines.push(line);
+        position += found + "<Task>".len();
+    }
+    lines
+}
+
+// Working minutes per day of the project calendar, used when the file lacks MinutesPerDay
+fn calendar_minutes_per_day(project: &Project) -> Option<i64> {
+    let calendar = project_calendar(project)?;
+
+    calendar
+        .week_days
+        .as_ref()?
+        .week_days
+        .iter()
+        .filter(|day| day.day_type > 0 && day.day_working == 1)
+        .filter_map(|day| day.working_times.as_ref())
+        .map(|times| {
+            times
+                .working_times
+                .iter()
+                .filter_map(|time| {
+                    let from = NaiveDateTime::parse_from_str(
+                        &format!("2000-01-01T{}", time.from_time),
+                        "%Y-%m-%dT%H:%M:%S",
+                    )
+                    .ok()?;
+                    let to = NaiveDateTime::parse_from_str(
+                        &format!("2000-01-01T{}", time.to_time),
+                        "%Y-%m-%dT%H:%M:%S",
+                    )
+                    .ok()?;
+                    Some((to - from).num_minutes())
+                })
+                .sum::<i64>()
+        })
+        .find(|minutes| *minutes > 0)
+}
+
+// Predecessor in the notation of the MS Project CSV export, e.g. "3" or "4SS+5 days"
+fn format_link(id: i64, link: &PredecessorLink, lag_days: i64) -> String {
+    let link_type = match link.link_type.unwrap_or(1) {
+        0 => LinkType::FinishToFinish,
+        2 => LinkType::StartToFinish,
+        3 => LinkType::StartToStart,
+        _ => LinkType::FinishToStart,
+    };
+    Link::new(id, link_type, lag_days).to_string()
+}
+
+// Lag in working days. LinkLag counts tenths of minutes of working time, of elapsed time for
+// the elapsed formats, and the percentage of the predecessor's duration for the percent ones.
+fn lag_days(
+    link: &PredecessorLink,
+    minutes_per_day: i64,
+    workdays_per_week: usize,
+    predecessor_days: i32,
+) -> i64 {
+    let lag = link.link_lag.unwrap_or(0) as f64;
+    let days = match link.lag_format.unwrap_or(7) {
+        4 | 6 | 8 | 10 | 12 => {
+            lag / 10.0 / MINUTES_PER_ELAPSED_DAY * workdays_per_week as f64 / 7.0
+        }
+        19 | 20 => lag / 100.0 * predecessor_days as f64,
+        _ => lag / 10.0 / minutes_per_day as f64,
+    };
+    days.round() as i64
+}
+
+// Parsing durations like "PT120H0M0S" into minutes
+fn parse_duration(duration: &str) -> Option<i64> {
+    let time = duration.strip_prefix("PT")?;
+    let (hours, rest) = time.split_once('H')?;
+    let (minutes, _) = rest.split_once('M')?;
+    Some(hours.parse::<i64>().ok()? * 60 + minutes.parse::<f64>().ok()? as i64)
+}
+
+fn parse_date(date: Option<&str>) -> Option<NaiveDate> {
+    NaiveDateTime::parse_from_str(date?, "%Y-%m-%dT%H:%M:%S")
+        .ok()
+        .map(|date_time| date_time.date())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations_into_minutes() {
+        assert_eq!(parse_duration("PT120H0M0S"), Some(7200));
+        assert_eq!(parse_duration("PT7H30M0S"), Some(450));
+        assert_eq!(parse_duration("PT0H0M0S"), Some(0));
+        assert_eq!(parse_duration("PT1H0.5M0S"), Some(60));
+        assert_eq!(parse_duration("P1D"), None);
+        assert_eq!(parse_duration("PTxH0M0S"), None);
+    }
+
+    #[test]
+    fn parses_dates_and_drops_the_time() {
+        assert_eq!(
+            parse_date(Some("2023-08-25T17:00:00")),
+            NaiveDate::from_ymd_opt(2023, 8, 25)
+        );
+        assert_eq!(parse_date(Some("2023-08-25")), None);
+        assert_eq!(parse_date(None), None);
+    }
+
+    #[test]
+    fn working_minutes_come_from_the_project_calendar() {
+        let xml = "<Project><CalendarUID>2</CalendarUID><Calendars>\
+            <Calendar><UID>1</UID><WeekDays><WeekDay><DayType>2</DayType>\
+            <DayWorking>1</DayWorking><WorkingTimes><WorkingTime><FromTime>08:00:00</FromTime>\
+            <ToTime>17:00:00</ToTime></WorkingTime></WorkingTimes></WeekDay></WeekDays></Calendar>\
+            <Calendar><UID>2</UID><WeekDays><WeekDay><DayType>1</DayType>\
+            <DayWorking>0</DayWorking></WeekDay><WeekDay><DayType>2</DayType>\
+            <DayWorking>1</DayWorking><WorkingTimes><WorkingTime><FromTime>08:00:00</FromTime>\
+            <ToTime>12:00:00</ToTime></WorkingTime><WorkingTime><FromTime>13:00:00</FromTime>\
+            <ToTime>16:30:00</ToTime></WorkingTime></WorkingTimes></WeekDay></WeekDays>\
+            </Calendar></Calendars></Project>";
+        let project: Project = quick_xml::de::from_str(xml).unwrap();
+        assert_eq!(calendar_minutes_per_day(&project), Some(450));
+
+        let project: Project = quick_xml::de::from_str("<Project></Project>").unwrap();
+        assert_eq!(calendar_minutes_per_day(&project), None);
+    }
+
+    #[test]
+    fn reads_weekdays_and_days_off_of_the_project_calendar() {
+        // Calendar 2 works Saturdays instead of Mondays and takes the rest from Standard
+        let xml = "<Project><CalendarUID>2</CalendarUID><Calendars>\
+            <Calendar><UID>1</UID><WeekDays>\
+            <WeekDay><DayType>1</DayType><DayWorking>0</DayWorking></WeekDay>\
+            <WeekDay><DayType>2</DayType><DayWorking>1</DayWorking></WeekDay>\
+            <WeekDay><DayType>3</DayType><DayWorking>1</DayWorking></WeekDay>\
+            <WeekDay><DayType>4</DayType><DayWorking>1</DayWorking></WeekDay>\
+            <WeekDay><DayType>5</DayType><DayWorking>1</DayWorking></WeekDay>\
+            <WeekDay><DayType>6</DayType><DayWorking>1</DayWorking></WeekDay>\
+            <WeekDay><DayType>7</DayType><DayWorking>0</DayWorking></WeekDay>\
+            </WeekDays></Calendar>\
+            <Calendar><UID>2</UID><BaseCalendarUID>1</BaseCalendarUID><WeekDays>\
+            <WeekDay><DayType>2</DayType><DayWorking>0</DayWorking></WeekDay>\
+            <WeekDay><DayType>7</DayType><DayWorking>1</DayWorking></WeekDay>\
+            <WeekDay><DayType>0</DayType><DayWorking>0</DayWorking><TimePeriod>\
+            <FromDate>2026-12-24T00:00:00</FromDate><ToDate>2026-12-26T23:59:00</ToDate>\
+            </TimePeriod></WeekDay>\
+            </WeekDays><Exceptions>\
+            <Exception><Name>New Year</Name><DayWorking>0</DayWorking><TimePeriod>\
+            <FromDate>2027-01-01T00:00:00</FromDate><ToDate>2027-01-01T23:59:00</ToDate>\
+            </TimePeriod></Exception>\
+            <Exception><Name>Overtime</Name><DayWorking>1</DayWorking><TimePeriod>\
+            <FromDate>2027-01-02T00:00:00</FromDate><ToDate>2027-01-02T23:59:00</ToDate>\
+            </TimePeriod></Exception>\
+            </Exceptions></Calendar></Calendars></Project>";
+        let project: Project = quick_xml::de::from_str(xml).unwrap();
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+        assert_eq!(
+            plan_calendar(&project),
+            Some(PlanCalendar {
+                weekdays: vec![
+                    Weekday::Tue,
+                    Weekday::Wed,
+                    Weekday::Thu,
+                    Weekday::Fri,
+                    Weekday::Sat
+                ],
+                days_off: vec![
+                    (date(2026, 12, 24), date(2026, 12, 26), String::new()),
+                    (date(2027, 1, 1), date(2027, 1, 1), "New Year".to_string()),
+                ],
+            })
+        );
+        let project: Project = quick_xml::de::from_str("<Project></Project>").unwrap();
+        assert_eq!(plan_calendar(&project), None);
+    }
+
+    #[test]
+    fn lag_follows_its_format() {
+        let link = |lag, format| PredecessorLink {
+            predecessor_uid: 1,
+            link_type: None,
+            link_lag: Some(lag),
+            lag_format: Some(format),
+        };
+        // Two working days of 8 hours
+        assert_eq!(lag_days(&link(9600, 7), 480, 5, 10), 2);
+        // Seven elapsed days are a working week
+        assert_eq!(lag_days(&link(100800, 8), 480, 5, 10), 5);
+        // Half of a ten day predecessor
+        assert_eq!(lag_days(&link(50, 19), 480, 5, 10), 5);
+        assert_eq!(lag_days(&link(-4800, 5), 480, 5, 10), -1);
+    }
+
+    #[test]
+    fn loads_the_example_plan() {
+        let (tasks, problems) = load_from_xml("examples/tasks-example.xml").unwrap();
+        assert!(problems.is_empty());
+        // The project summary task is left out
+        assert_eq!(tasks.len(), 9);
+
+        let requirements = tasks.iter().find(|task| task.id == 2).unwrap();
+        assert_eq!(requirements.name, "Requirements");
+        assert_eq!(requirements.duration, 15);
+        assert_eq!(requirements.predecessors, "1");
+        assert_eq!(requirements.total_slack, 20);
+        assert_eq!(
+            requirements.start_date,
+            NaiveDate::from_ymd_opt(2023, 8, 7).unwrap()
+        );
+        assert_eq!(requirements.line, Some(112));
+    }
+
+    #[test]
+    fn links_keep_their_type_and_lag() {
+        let xml = "<Project><Tasks>\
+            <Task><UID>1</UID><ID>1</ID><Name>Design</Name><Start>2026-10-19T08:00:00</Start>\
+            <Finish>2026-10-19T17:00:00</Finish><Duration>PT8H0M0S</Duration></Task>\
+            <Task><UID>7</UID><ID>2</ID><Name>Build</Name><Start>2026-10-19T08:00:00</Start>\
+            <Finish>2026-10-20T17:00:00</Finish><Duration>PT16H0M0S</Duration>\
+            <PredecessorLink><PredecessorUID>1</PredecessorUID><Type>3</Type>\
+            <LinkLag>4800</LinkLag></PredecessorLink>\
+            <PredecessorLink><PredecessorUID>99</PredecessorUID></PredecessorLink></Task>\
+            </Tasks></Project>";
+        let path = std::env::temp_dir().join("project-manager-links.xml");
+        fs::write(&path, xml).unwrap();
+
+        let (tasks, problems) = load_from_xml(path.to_str().unwrap()).unwrap();
+        assert_eq!(tasks[1].id, 2);
+        assert_eq!(tasks[1].duration, 2);
+        assert_eq!(tasks[1].predecessors, "1SS+1 day");
+        // The unknown predecessor is reported on the task that links to it
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].task_id, Some(2));
+        assert!(problems[0]
+            .message
+            .contains("Predecessor with UID 99 does not exist"));
+    }
+}
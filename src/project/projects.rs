@@ -0,0 +1,180 @@
+use anyhow::{anyhow, bail};
+use colored::Colorize;
+use prettytable::{row, Table};
+use sqlx::SqlitePool;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Project {
+    pub(crate) id: i64,
+    pub(crate) name: String,
+}
+
+// Project given with --project, or the one switched to last
+pub(crate) async fn select(pool: &SqlitePool, name: &Option<String>) -> anyhow::Result<Project> {
+    match name {
+        Some(name) => find(pool, name).await,
+        None => sqlx::query_as!(
+            Project,
+            r#"
+            SELECT id as "id!", name
+            FROM projects
+            ORDER BY is_current DESC, id
+            LIMIT 1
+            "#
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| anyhow!("No projects yet, create one with `project new`")),
+    }
+}
+
+pub(crate) async fn find(pool: &SqlitePool, name: &str) -> anyhow::Result<Project> {
+    sqlx::query_as!(
+        Project,
+        r#"SELECT id as "id!", name FROM projects WHERE name = ?1"#,
+        name
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| anyhow!("No project named {}, see `project list`", name))
+}
+
+// Create a project and switch to it, so the next import fills it
+pub(crate) async fn new(pool: &SqlitePool, name: &str) -> anyhow::Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("Project needs a name");
+    }
+    if find(pool, name).await.is_ok() {
+        bail!("Project {} already exists", name);
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::query!(r#"UPDATE projects SET is_current = 0"#)
+        .execute(&mut *tx)
+        .await?;
+    let id = sqlx::query!(
+        r#"INSERT INTO projects (name, is_current) VALUES (?1, 1)"#,
+        name
+    )
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+    tx.commit().await?;
+
+    println!(
+        "🗂️ Created project {} (id: {}) and switched to it, fill it with `import`",
+        name, id
+    );
+    Ok(())
+}
+
+pub(crate) async fn switch(pool: &SqlitePool, name: &str) -> anyhow::Result<()> {
+    let project = find(pool, name).await?;
+    let mut tx = pool.begin().await?;
+    sqlx::query!(r#"UPDATE projects SET is_current = 0"#)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query!(
+        r#"UPDATE projects SET is_current = 1 WHERE id = ?1"#,
+        project.id
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    println!("🔀 Switched to project {}", project.name);
+    Ok(())
+}
+
+pub(crate) async fn list(pool: &SqlitePool, current_id: i64) -> anyhow::Result<()> {
+    let projects = sqlx::query!(
+        r#"
+        SELECT p.id                  as "id!",
+               p.name                as "name!",
+               p.created_at          as created_at,
+               count(t.id)           as "tasks!: i64",
+               count(td.finished_at) as "finished!: i64",
+               max(t.finish_date)    as finish_date
+        FROM projects p
+                 LEFT OUTER JOIN tasks t ON t.project_id = p.id AND t.duration > 0
+                 LEFT OUTER JOIN task_data td ON td.project_id = t.project_id AND td.task_id = t.id
+        GROUP BY p.id
+        ORDER BY p.id
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "".bold(),
+        "Project".bold(),
+        "Created".bold(),
+        "Tasks".bold(),
+        "Done".bold(),
+        "Planned Finish Date".bold(),
+    ]);
+    for project in projects {
+        // Marks the project switched to, or the one given with --project
+        let marker = if project.id == current_id {
+            "*".green().bold()
+        } else {
+            "".normal()
+        };
+        table.add_row(row![
+            marker,
+            project.name,
+            project.created_at.unwrap_or_default(),
+            project.tasks.to_string(),
+            project.finished.to_string(),
+            project.finish_date.unwrap_or("--".to_string()),
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+// Nothing imported into the project yet
+pub(crate) async fn is_empty(pool: &SqlitePool, project_id: i64) -> anyhow::Result<bool> {
+    let tasks = sqlx::query!(
+        r#"SELECT count(*) as "tasks!: i64" FROM tasks WHERE project_id = ?1"#,
+        project_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(tasks.tasks == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{test_pool, TEST_PROJECT};
+
+    #[tokio::test]
+    async fn selects_the_named_or_the_current_project() {
+        let pool = test_pool().await;
+        assert_eq!(select(&pool, &None).await.unwrap().id, TEST_PROJECT);
+
+        new(&pool, "Second").await.unwrap();
+        let second = select(&pool, &None).await.unwrap();
+        assert_eq!(second.name, "Second");
+        assert_ne!(second.id, TEST_PROJECT);
+
+        switch(&pool, "default").await.unwrap();
+        assert_eq!(select(&pool, &None).await.unwrap().id, TEST_PROJECT);
+        // --project picks another one without switching to it
+        let named = select(&pool, &Some("Second".to_string())).await.unwrap();
+        assert_eq!(named.id, second.id);
+        assert_eq!(select(&pool, &None).await.unwrap().id, TEST_PROJECT);
+
+        let error = select(&pool, &Some("Unknown".to_string()))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "No project named Unknown, see `project list`"
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use colored::{Color, ColoredString, Colorize};
+use prettytable::{row, Table};
+use sqlx::SqlitePool;
+
+use crate::config::{Config, SlackThresholds};
+
+// Plans with a higher risk are fragile, below the minimum they are decompressed more than
+// is worth paying for
+const MAX_RISK: f64 = 0.75;
+const MIN_RISK: f64 = 0.3;
+
+// Activity risk this far above criticality risk means a task with far more float than the
+// others skews it, the largest float is what activity risk measures against
+const SKEW: f64 = 0.2;
+
+// Float buckets of The Method, weighted by how likely the tasks are to delay the project
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum FloatBucket {
+    Critical,
+    Red,
+    Yellow,
+    Green,
+}
+
+impl FloatBucket {
+    // The red and yellow thresholds are configured
+    pub(crate) fn of(slack: i64, thresholds: &SlackThresholds) -> Self {
+        if slack <= 0 {
+            FloatBucket::Critical
+        } else if slack <= thresholds.red {
+            FloatBucket::Red
+        } else if slack <= thresholds.yellow {
+            FloatBucket::Yellow
+        } else {
+            FloatBucket::Green
+        }
+    }
+
+    fn weight(self) -> usize {
+        match self {
+            FloatBucket::Critical => 4,
+            FloatBucket::Red => 3,
+            FloatBucket::Yellow => 2,
+            FloatBucket::Green => 1,
+        }
+    }
+}
+
+// Risk of a plan given the float of its activities, milestones are left out
+struct RiskMetrics {
+    activities: usize,
+    critical: usize,
+    red: usize,
+    yellow: usize,
+    green: usize,
+    criticality_risk: f64,
+    activity_risk: f64,
+}
+
+impl RiskMetrics {
+    fn of(floats: &[i64], thresholds: &SlackThresholds) -> Option<Self> {
+        if floats.is_empty() {
+            return None;
+        }
+        let buckets: Vec<FloatBucket> = floats
+            .iter()
+            .map(|float| FloatBucket::of(*float, thresholds))
+            .collect();
+        let count = |bucket: FloatBucket| buckets.iter().filter(|b| **b == bucket).count();
+        let activities = floats.len();
+
+        // Criticality risk weighs the buckets, 0.25 when all tasks are green and 1 when all
+        // are critical
+        let criticality_risk = buckets.iter().map(|bucket| bucket.weight()).sum::<usize>() as f64
+            / (FloatBucket::Critical.weight() * activities) as f64;
+
+        // Activity risk compares the float of each task to the largest float in the plan
+        let max_float = floats.iter().copied().max().unwrap_or(0).max(0);
+        let activity_risk = if max_float == 0 {
+            1.0
+        } else {
+            1.0 - floats.iter().map(|float| (*float).max(0)).sum::<i64>() as f64
+                / (activities as i64 * max_float) as f64
+        };
+
+        Some(RiskMetrics {
+            activities,
+            critical: count(FloatBucket::Critical),
+            red: count(FloatBucket::Red),
+            yellow: count(FloatBucket::Yellow),
+            green: count(FloatBucket::Green),
+            criticality_risk,
+            activity_risk,
+        })
+    }
+}
+
+// Floats of a plan to assess, `since` names it in the trend of the risk
+struct Plan {
+    name: String,
+    date: String,
+    since: String,
+    floats: Vec<i64>,
+}
+
+// Criticality and activity risk of the current plan, of the plan as designed in MS Project and
+// of every baseline, so rising risk shows the project getting fragile as float is consumed
+pub(crate) async fn report(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let tasks = sqlx::query!(
+        r#"
+        SELECT total_slack, pdex_criticality
+        FROM tasks
+        WHERE project_id = ?1
+          AND duration > 0
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+    if tasks.is_empty() {
+        println!("🎲 No tasks to assess, import a plan first");
+        return Ok(());
+    }
+
+    let baselines = sqlx::query!(
+        r#"
+        SELECT b.id           as "id!",
+               b.name         as "name!",
+               b.created_at   as created_at,
+               bt.total_slack as total_slack
+        FROM baselines b
+                 JOIN baseline_tasks bt ON b.id = bt.baseline_id
+        WHERE b.project_id = ?1
+          AND bt.duration > 0
+        ORDER BY b.created_at, b.id
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // Plans in the order they were made, the current one last
+    let mut plans: Vec<Plan> = vec![];
+    // The design float MS Project exported, milestones are marked with a negative value
+    let designed: Vec<i64> = tasks
+        .iter()
+        .filter_map(|task| task.pdex_criticality)
+        .filter(|float| *float >= 0)
+        .collect();
+    if !designed.is_empty() {
+        plans.push(Plan {
+            name: "As designed".to_string(),
+            date: "--".to_string(),
+            since: "the design".to_string(),
+            floats: designed,
+        });
+    }
+    let mut baseline_id = None;
+    for row in baselines {
+        if baseline_id != Some(row.id) {
+            baseline_id = Some(row.id);
+            plans.push(Plan {
+                name: format!("Baseline {}", row.name),
+                date: row.created_at.unwrap_or_default(),
+                since: format!("baseline {}", row.name),
+                floats: vec![],
+            });
+        }
+        if let Some(plan) = plans.last_mut() {
+            plan.floats.push(row.total_slack);
+        }
+    }
+    plans.push(Plan {
+        name: "Current plan".to_string(),
+        date: "now".to_string(),
+        since: String::new(),
+        floats: tasks.iter().map(|task| task.total_slack).collect(),
+    });
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "Plan".bold(),
+        "Date".bold(),
+        "Activities".bold(),
+        "Critical".bold(),
+        "Red".bold(),
+        "Yellow".bold(),
+        "Green".bold(),
+        "Criticality Risk".bold(),
+        "Activity Risk".bold(),
+        "Assessment".bold(),
+    ]);
+    let mut history = vec![];
+    for plan in &plans {
+        let Some(metrics) = RiskMetrics::of(&plan.floats, &config.slack) else {
+            continue;
+        };
+        table.add_row(row![
+            plan.name,
+            plan.date,
+            metrics.activities.to_string(),
+            metrics.critical.to_string(),
+            metrics.red.to_string(),
+            metrics.yellow.to_string(),
+            metrics.green.to_string(),
+            risk_value(metrics.criticality_risk),
+            risk_value(metrics.activity_risk),
+            assessment(metrics.criticality_risk),
+        ]);
+        history.push((&plan.since, metrics));
+    }
+    table.printstd();
+
+    let Some((_, current)) = history.pop() else {
+        return Ok(());
+    };
+    if current.activity_risk - current.criticality_risk > SKEW {
+        println!(
+            "⚠️ Activity risk is well above criticality risk, a task with far more float than the others makes the plan look riskier than it is"
+        );
+    }
+    if let Some((since, first)) = history.first() {
+        let change = current.criticality_risk - first.criticality_risk;
+        if change > 0.0 {
+            println!(
+                "📈 Criticality risk rose by {:.2} since {}, float is being consumed{}",
+                change,
+                since,
+                if current.criticality_risk > MAX_RISK {
+                    " and the project is fragile now"
+                } else {
+                    ""
+                }
+            );
+        } else {
+            println!("📉 Criticality risk did not rise since {}", since);
+        }
+    }
+    Ok(())
+}
+
+fn risk_color(risk: f64) -> Color {
+    if risk > MAX_RISK {
+        Color::Red
+    } else if risk < MIN_RISK {
+        Color::Yellow
+    } else {
+        Color::Green
+    }
+}
+
+fn risk_value(risk: f64) -> ColoredString {
+    format!("{:.2}", risk).color(risk_color(risk))
+}
+
+fn assessment(risk: f64) -> ColoredString {
+    let assessment = if risk > MAX_RISK {
+        "fragile"
+    } else if risk < MIN_RISK {
+        "over-decompressed"
+    } else {
+        "healthy"
+    };
+    assessment.color(risk_color(risk))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_follow_the_default_thresholds() {
+        let thresholds = SlackThresholds::default();
+        assert_eq!(FloatBucket::of(-3, &thresholds), FloatBucket::Critical);
+        assert_eq!(FloatBucket::of(0, &thresholds), FloatBucket::Critical);
+        assert_eq!(FloatBucket::of(1, &thresholds), FloatBucket::Red);
+        assert_eq!(FloatBucket::of(10, &thresholds), FloatBucket::Red);
+        assert_eq!(FloatBucket::of(11, &thresholds), FloatBucket::Yellow);
+        assert_eq!(FloatBucket::of(30, &thresholds), FloatBucket::Yellow);
+        assert_eq!(FloatBucket::of(31, &thresholds), FloatBucket::Green);
+    }
+
+    #[test]
+    fn buckets_follow_the_configured_thresholds() {
+        let thresholds = SlackThresholds { red: 3, yellow: 5 };
+        assert_eq!(FloatBucket::of(3, &thresholds), FloatBucket::Red);
+        assert_eq!(FloatBucket::of(4, &thresholds), FloatBucket::Yellow);
+        assert_eq!(FloatBucket::of(6, &thresholds), FloatBucket::Green);
+
+        let metrics = RiskMetrics::of(&[2, 4, 6], &thresholds).unwrap();
+        assert_eq!((metrics.red, metrics.yellow, metrics.green), (1, 1, 1));
+    }
+
+    #[test]
+    fn criticality_risk_weighs_the_buckets() {
+        let risk = |floats: &[i64]| {
+            RiskMetrics::of(floats, &SlackThresholds::default())
+                .unwrap()
+                .criticality_risk
+        };
+        assert_eq!(risk(&[40, 50, 60]), 0.25);
+        assert_eq!(risk(&[0, 0, -2]), 1.0);
+        assert_eq!(risk(&[0, 5, 20, 40]), 0.625);
+    }
+
+    #[test]
+    fn activity_risk_compares_floats_to_the_largest() {
+        let risk = |floats: &[i64]| {
+            RiskMetrics::of(floats, &SlackThresholds::default())
+                .unwrap()
+                .activity_risk
+        };
+        assert_eq!(risk(&[0, 5, 20, 40]), 1.0 - 65.0 / 160.0);
+        assert_eq!(risk(&[10, 10]), 0.0);
+        assert_eq!(risk(&[0, 0]), 1.0);
+        // Negative float counts like none
+        assert_eq!(risk(&[-5, 10]), 0.5);
+    }
+
+    #[test]
+    fn counts_tasks_per_bucket() {
+        let thresholds = SlackThresholds::default();
+        let metrics = RiskMetrics::of(&[0, 5, 20, 40, 50], &thresholds).unwrap();
+        assert_eq!(metrics.activities, 5);
+        assert_eq!(
+            (metrics.critical, metrics.red, metrics.yellow, metrics.green),
+            (1, 1, 1, 2)
+        );
+        assert!(RiskMetrics::of(&[], &thresholds).is_none());
+    }
+}
@@ -0,0 +1,692 @@
+use std::collections::{BTreeSet, HashMap};
+
+use anyhow::bail;
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use colored::Colorize;
+use prettytable::{row, Table};
+use sqlx::SqlitePool;
+
+use crate::config::Config;
+use crate::project::calendar::{self, Calendar, WorkDays};
+use crate::project::dependency::{self, Link, LinkType};
+use crate::project::{dfmt, float};
+
+// A task as the scheduler sees it, with the dates currently stored in the database
+pub(crate) struct Activity {
+    pub(crate) id: i64,
+    pub(crate) name: String,
+    duration: i64,
+    predecessors: Vec<Link>,
+    pub(crate) start_date: NaiveDate,
+    finish_date: NaiveDate,
+    total_slack: i64,
+    pub(crate) finished_at: Option<NaiveDate>,
+    pub(crate) logged: i64,
+    pub(crate) leveling_delay: i64,
+    // Calendar of the assignee, their vacations and working weekdays stretch the task
+    calendar: Option<Calendar>,
+}
+
+impl Activity {
+    // Tasks without predecessors keep their stored start, so holding them back moves that start
+    pub(crate) fn delay(&mut self, workdays: i64, calendar: &Calendar) {
+        if self.predecessors.is_empty() {
+            self.start_date = calendar.add_workdays(self.start_date, workdays);
+        } else {
+            self.leveling_delay += workdays;
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct ScheduledTask {
+    pub(crate) id: i64,
+    pub(crate) early_start: NaiveDate,
+    pub(crate) early_finish: NaiveDate,
+    pub(crate) late_start: NaiveDate,
+    pub(crate) late_finish: NaiveDate,
+    pub(crate) total_slack: i64,
+    pub(crate) free_slack: i64,
+}
+
+// Schedule entry for a task whose dates or slack moved
+pub(crate) struct ScheduleChange {
+    id: i64,
+    name: String,
+    start_date: NaiveDate,
+    finish_date: NaiveDate,
+    total_slack: i64,
+    scheduled: ScheduledTask,
+}
+
+pub(crate) async fn schedule(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    status_date: Option<NaiveDate>,
+) -> anyhow::Result<()> {
+    let (scheduled, changes) = reschedule(pool, project_id, status_date).await?;
+
+    if changes.is_empty() {
+        println!("📅 Schedule is up to date, no tasks moved");
+    } else {
+        let mut table = Table::new();
+        table.add_row(row![
+            "#".bold(),
+            "Task".bold(),
+            "Start Date".bold(),
+            "Finish Date".bold(),
+            "Slack".bold(),
+        ]);
+        for change in &changes {
+            let finish = format!(
+                "{} → {}",
+                dfmt(change.finish_date, &config.dates),
+                dfmt(change.scheduled.early_finish, &config.dates)
+            );
+            let finish = if change.scheduled.early_finish > change.finish_date {
+                finish.red()
+            } else {
+                finish.normal()
+            };
+            table.add_row(row![
+                change.id.to_string(),
+                change.name,
+                format!(
+                    "{} → {}",
+                    dfmt(change.start_date, &config.dates),
+                    dfmt(change.scheduled.early_start, &config.dates)
+                ),
+                finish,
+                format!("{} → {}", change.total_slack, change.scheduled.total_slack),
+            ]);
+        }
+        table.printstd();
+    }
+
+    if let Some(project_finish) = scheduled.iter().map(|task| task.early_finish).max() {
+        let critical_tasks = scheduled
+            .iter()
+            .filter(|task| task.total_slack <= 0)
+            .count();
+        println!(
+            "📅 Project finishes {}, {} tasks on the critical path",
+            dfmt(project_finish, &config.dates),
+            critical_tasks
+        );
+    }
+    Ok(())
+}
+
+// Reschedule after progress was recorded and tell the user when the plan moved
+pub(crate) async fn update(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let (scheduled, changes) = reschedule(pool, project_id, None).await?;
+    if let Some(project_finish) = scheduled.iter().map(|task| task.early_finish).max() {
+        if !changes.is_empty() {
+            println!(
+                "📅 Rescheduled {} tasks, project finishes {}",
+                changes.len(),
+                dfmt(project_finish, &config.dates)
+            );
+        }
+    }
+    Ok(())
+}
+
+// Recalculate early/late dates and float for all tasks and store them, returning the new
+// schedule together with the tasks that moved
+pub(crate) async fn reschedule(
+    pool: &SqlitePool,
+    project_id: i64,
+    status_date: Option<NaiveDate>,
+) -> anyhow::Result<(Vec<ScheduledTask>, Vec<ScheduleChange>)> {
+    let activities = load_activities(pool, project_id).await?;
+    let calendar = calendar::project_calendar(pool, project_id).await?;
+    let scheduled = compute(&activities, status_date, &calendar)?;
+
+    let mut tx = pool.begin().await?;
+    for task in &scheduled {
+        let start_date = task.early_start.format("%Y-%m-%d").to_string();
+        let finish_date = task.early_finish.format("%Y-%m-%d").to_string();
+        let late_start = task.late_start.format("%Y-%m-%d").to_string();
+        let late_finish = task.late_finish.format("%Y-%m-%d").to_string();
+        sqlx::query!(
+            r#"
+            UPDATE tasks
+            SET start_date  = ?1,
+                finish_date = ?2,
+                late_start  = ?3,
+                late_finish = ?4,
+                total_slack = ?5,
+                free_slack  = ?6
+            WHERE project_id = ?8
+              AND id = ?7
+            "#,
+            start_date,
+            finish_date,
+            late_start,
+            late_finish,
+            task.total_slack,
+            task.free_slack,
+            task.id,
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    float::snapshot(&mut tx, project_id, Local::now().date_naive()).await?;
+    tx.commit().await?;
+
+    let changes = activities
+        .into_iter()
+        .zip(scheduled.iter())
+        .filter(|(activity, task)| {
+            activity.start_date != task.early_start
+                || activity.finish_date != task.early_finish
+                || activity.total_slack != task.total_slack
+        })
+        .map(|(activity, task)| ScheduleChange {
+            id: activity.id,
+            name: activity.name,
+            start_date: activity.start_date,
+            finish_date: activity.finish_date,
+            total_slack: activity.total_slack,
+            scheduled: task.clone(),
+        })
+        .collect();
+
+    Ok((scheduled, changes))
+}
+
+pub(crate) async fn load_activities(
+    pool: &SqlitePool,
+    project_id: i64,
+) -> anyhow::Result<Vec<Activity>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.id           as "id!",
+               t.name         as name,
+               t.duration     as duration,
+               t.start_date   as start_date,
+               t.finish_date  as finish_date,
+               t.total_slack  as total_slack,
+               t.leveling_delay as leveling_delay,
+               td.finished_at as finished_at,
+               td.member_id   as member_id,
+               (SELECT sum(ts.duration)
+                FROM timesheet ts
+                WHERE ts.project_id = t.project_id
+                  AND ts.task_id = t.id) as "logged: i64"
+        FROM tasks t
+                 LEFT OUTER JOIN task_data td
+                                 ON td.project_id = t.project_id AND td.task_id = t.id
+        WHERE t.project_id = ?1
+        ORDER BY t.id
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut links = dependency::load(pool, project_id).await?;
+    let calendars = calendar::member_calendars(pool, project_id).await?;
+    let mut activities = vec![];
+    for row in rows {
+        activities.push(Activity {
+            id: row.id,
+            name: row.name,
+            duration: row.duration,
+            predecessors: links.remove(&row.id).unwrap_or_default(),
+            start_date: row.start_date.parse()?,
+            finish_date: row.finish_date.parse()?,
+            total_slack: row.total_slack,
+            finished_at: row
+                .finished_at
+                .and_then(|date_str| {
+                    NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").ok()
+                })
+                .map(|naive_datetime| naive_datetime.date()),
+            logged: row.logged.unwrap_or(0),
+            leveling_delay: row.leveling_delay,
+            calendar: row
+                .member_id
+                .and_then(|member_id| calendars.get(&member_id).cloned()),
+        });
+    }
+    Ok(activities)
+}
+
+// Forward and backward pass over the task network. Dates are converted to working day
+// numbers of the project calendar, early/late finish are exclusive while the stored finish dates
+// are the last day worked. Links tie the start or finish of a task to the start or finish of its
+// predecessor, shifted by the lag.
+pub(crate) fn compute(
+    activities: &[Activity],
+    status_date: Option<NaiveDate>,
+    calendar: &Calendar,
+) -> anyhow::Result<Vec<ScheduledTask>> {
+    let Some(anchor) = activities.iter().map(|activity| activity.start_date).min() else {
+        return Ok(vec![]);
+    };
+    let days = WorkDays::new(calendar, anchor);
+    let status = status_date.map(|date| days.index(date));
+
+    let by_id: HashMap<i64, &Activity> = activities
+        .iter()
+        .map(|activity| (activity.id, activity))
+        .collect();
+    let order = topological_order(activities)?;
+
+    // Forward pass
+    let mut early: HashMap<i64, (i64, i64)> = HashMap::new();
+    for id in &order {
+        let activity = by_id[id];
+        // Links to the finish of the task are met by starting early enough, but never before
+        // the project starts
+        let es = activity
+            .predecessors
+            .iter()
+            .map(|link| {
+                let (predecessor_es, predecessor_ef) = early[&link.predecessor];
+                match link.link_type {
+                    LinkType::FinishToStart => predecessor_ef + link.lag,
+                    LinkType::StartToStart => predecessor_es + link.lag,
+                    LinkType::FinishToFinish => predecessor_ef + link.lag - activity.duration,
+                    LinkType::StartToFinish => predecessor_es + link.lag - activity.duration,
+                }
+                .max(0)
+            })
+            .max()
+            .unwrap_or_else(|| days.index(activity.start_date));
+
+        let (es, ef) = match activity.finished_at {
+            Some(finished_at) => {
+                let ef = days.index(finished_at) + i64::from(calendar.is_workday(finished_at));
+                (es.min(ef), ef)
+            }
+            None if activity.logged > 0 => {
+                // Started work keeps its start, the remaining estimate is never less than a day
+                let remaining = (activity.duration - activity.logged).max(1);
+                let resume = status.map_or(es + activity.logged, |status| {
+                    status.max(es + activity.logged)
+                });
+                let (_, ef) = assignee_days(&days, calendar, activity, resume, remaining);
+                (es, ef)
+            }
+            None => {
+                let es = if activity.predecessors.is_empty() {
+                    es
+                } else {
+                    es + activity.leveling_delay
+                };
+                let es = status.map_or(es, |status| es.max(status));
+                assignee_days(&days, calendar, activity, es, activity.duration)
+            }
+        };
+        early.insert(*id, (es, ef));
+    }
+
+    let project_finish = early.values().map(|(_, ef)| *ef).max().unwrap_or(0);
+
+    let mut successors: HashMap<i64, Vec<(i64, Link)>> = HashMap::new();
+    for activity in activities {
+        for link in &activity.predecessors {
+            successors
+                .entry(link.predecessor)
+                .or_default()
+                .push((activity.id, *link));
+        }
+    }
+
+    // Backward pass
+    let mut late: HashMap<i64, (i64, i64)> = HashMap::new();
+    for id in order.iter().rev() {
+        let (es, ef) = early[id];
+        let lf = successors
+            .get(id)
+            .into_iter()
+            .flatten()
+            .map(|(successor, link)| {
+                let (successor_ls, successor_lf) = late[successor];
+                match link.link_type {
+                    LinkType::FinishToStart => successor_ls - link.lag,
+                    LinkType::StartToStart => successor_ls - link.lag + (ef - es),
+                    LinkType::FinishToFinish => successor_lf - link.lag,
+                    LinkType::StartToFinish => successor_lf - link.lag + (ef - es),
+                }
+            })
+            .min()
+            .unwrap_or(project_finish)
+            .min(project_finish);
+        late.insert(*id, (lf - (ef - es), lf));
+    }
+
+    let scheduled = activities
+        .iter()
+        .map(|activity| {
+            let (es, ef) = early[&activity.id];
+            let (ls, lf) = late[&activity.id];
+            let free_slack = successors
+                .get(&activity.id)
+                .into_iter()
+                .flatten()
+                .map(|(successor, link)| {
+                    let (successor_es, successor_ef) = early[successor];
+                    match link.link_type {
+                        LinkType::FinishToStart => successor_es - link.lag - ef,
+                        LinkType::StartToStart => successor_es - link.lag - es,
+                        LinkType::FinishToFinish => successor_ef - link.lag - ef,
+                        LinkType::StartToFinish => successor_ef - link.lag - es,
+                    }
+                })
+                .min()
+                .unwrap_or(project_finish - ef);
+
+            ScheduledTask {
+                id: activity.id,
+                early_start: days.date(es),
+                early_finish: days.finish_date(es, ef),
+                late_start: days.date(ls),
+                late_finish: days.finish_date(ls, lf),
+                total_slack: ls - es,
+                free_slack,
+            }
+        })
+        .collect();
+
+    Ok(scheduled)
+}
+
+// Start and exclusive finish of work starting at `es`, counted in project working days. An
+// assignee only works on the days of their own calendar, which can push both out.
+fn assignee_days(
+    days: &WorkDays,
+    project_calendar: &Calendar,
+    activity: &Activity,
+    es: i64,
+    duration: i64,
+) -> (i64, i64) {
+    let Some(calendar) = &activity.calendar else {
+        return (es, es + duration);
+    };
+    let start = calendar.add_workdays(days.date(es), 0);
+    let es = es.max(days.index(start));
+    if duration <= 0 {
+        return (es, es);
+    }
+    let last = calendar.add_workdays(start, duration - 1);
+    let ef = days.index(last) + i64::from(project_calendar.is_workday(last));
+    (es, ef.max(es + 1))
+}
+
+// Orders tasks so that every task comes after its predecessors
+fn topological_order(activities: &[Activity]) -> anyhow::Result<Vec<i64>> {
+    let mut remaining: HashMap<i64, usize> = activities
+        .iter()
+        .map(|activity| (activity.id, activity.predecessors.len()))
+        .collect();
+    let mut successors: HashMap<i64, Vec<i64>> = HashMap::new();
+    for activity in activities {
+        for link in &activity.predecessors {
+            if !remaining.contains_key(&link.predecessor) {
+                bail!(
+                    "Task #{} {} has an unknown predecessor #{}",
+                    activity.id,
+                    activity.name,
+                    link.predecessor
+                );
+            }
+            successors
+                .entry(link.predecessor)
+                .or_default()
+                .push(activity.id);
+        }
+    }
+
+    let mut ready: BTreeSet<i64> = remaining
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    let mut order = vec![];
+    while let Some(id) = ready.pop_first() {
+        order.push(id);
+        remaining.remove(&id);
+        for successor in successors.get(&id).into_iter().flatten() {
+            let count = remaining.get_mut(successor).expect("Unknown successor");
+            *count -= 1;
+            if *count == 0 {
+                ready.insert(*successor);
+            }
+        }
+    }
+
+    if !remaining.is_empty() {
+        let mut cycle: Vec<i64> = remaining.into_keys().collect();
+        cycle.sort();
+        bail!(
+            "Dependency cycle between tasks {}",
+            cycle
+                .iter()
+                .map(|id| format!("#{}", id))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        // Monday 19.10.26 is the first day of the project
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn activity(id: i64, duration: i64, predecessors: Vec<Link>) -> Activity {
+        Activity {
+            id,
+            name: format!("Task {}", id),
+            duration,
+            predecessors,
+            start_date: date(19),
+            finish_date: date(19),
+            total_slack: 0,
+            finished_at: None,
+            logged: 0,
+            leveling_delay: 0,
+            calendar: None,
+        }
+    }
+
+    fn after(predecessor: i64) -> Link {
+        Link::new(predecessor, LinkType::FinishToStart, 0)
+    }
+
+    fn task(scheduled: &[ScheduledTask], id: i64) -> &ScheduledTask {
+        scheduled.iter().find(|task| task.id == id).unwrap()
+    }
+
+    #[test]
+    fn forward_pass_starts_successors_after_predecessors() {
+        let activities = vec![
+            activity(1, 3, vec![]),
+            activity(2, 2, vec![after(1)]),
+            activity(3, 1, vec![after(1)]),
+            activity(4, 2, vec![after(2)]),
+        ];
+        let scheduled = compute(&activities, None, &Calendar::standard(&[])).unwrap();
+
+        assert_eq!(task(&scheduled, 1).early_start, date(19));
+        assert_eq!(task(&scheduled, 1).early_finish, date(21));
+        assert_eq!(task(&scheduled, 2).early_start, date(22));
+        assert_eq!(task(&scheduled, 2).early_finish, date(23));
+        assert_eq!(task(&scheduled, 3).early_finish, date(22));
+        // The weekend is skipped
+        assert_eq!(task(&scheduled, 4).early_start, date(26));
+        assert_eq!(task(&scheduled, 4).early_finish, date(27));
+    }
+
+    #[test]
+    fn backward_pass_finds_slack_and_critical_path() {
+        let activities = vec![
+            activity(1, 3, vec![]),
+            activity(2, 2, vec![after(1)]),
+            activity(3, 1, vec![after(1)]),
+            activity(4, 1, vec![after(2), after(3)]),
+        ];
+        let scheduled = compute(&activities, None, &Calendar::standard(&[])).unwrap();
+
+        for id in [1, 2, 4] {
+            assert_eq!(task(&scheduled, id).total_slack, 0, "task #{}", id);
+        }
+        let short = task(&scheduled, 3);
+        assert_eq!(short.total_slack, 1);
+        assert_eq!(short.free_slack, 1);
+        assert_eq!(short.late_start, date(23));
+        assert_eq!(short.late_finish, date(23));
+    }
+
+    #[test]
+    fn vacations_of_the_assignee_stretch_their_tasks() {
+        let mut on_vacation = activity(1, 3, vec![]);
+        on_vacation.calendar = Some(Calendar::standard(&[date(20), date(21)]));
+        let activities = vec![on_vacation, activity(2, 1, vec![after(1)])];
+        let scheduled = compute(&activities, None, &Calendar::standard(&[])).unwrap();
+
+        assert_eq!(task(&scheduled, 1).early_start, date(19));
+        assert_eq!(task(&scheduled, 1).early_finish, date(23));
+        assert_eq!(task(&scheduled, 2).early_start, date(26));
+    }
+
+    #[test]
+    fn holidays_are_skipped_for_everyone() {
+        let activities = vec![activity(1, 3, vec![]), activity(2, 1, vec![after(1)])];
+        let scheduled = compute(&activities, None, &Calendar::standard(&[date(20)])).unwrap();
+
+        assert_eq!(task(&scheduled, 1).early_finish, date(22));
+        assert_eq!(task(&scheduled, 2).early_start, date(23));
+    }
+
+    #[test]
+    fn open_work_does_not_start_before_the_status_date() {
+        let activities = vec![activity(1, 2, vec![]), activity(2, 1, vec![after(1)])];
+        let scheduled = compute(&activities, Some(date(22)), &Calendar::standard(&[])).unwrap();
+
+        assert_eq!(task(&scheduled, 1).early_start, date(22));
+        assert_eq!(task(&scheduled, 2).early_start, date(26));
+    }
+
+    #[test]
+    fn finished_and_started_work_keep_their_dates() {
+        let mut finished = activity(1, 3, vec![]);
+        finished.finished_at = Some(date(20));
+        let mut started = activity(2, 4, vec![after(1)]);
+        started.logged = 1;
+        let activities = vec![finished, started];
+        let scheduled = compute(&activities, Some(date(23)), &Calendar::standard(&[])).unwrap();
+
+        assert_eq!(task(&scheduled, 1).early_finish, date(20));
+        // One of four days is logged, the other three follow from the status date
+        assert_eq!(task(&scheduled, 2).early_start, date(21));
+        assert_eq!(task(&scheduled, 2).early_finish, date(27));
+    }
+
+    #[test]
+    fn typed_links_tie_starts_and_finishes_with_lag() {
+        let link = |link_type, lag| vec![Link::new(1, link_type, lag)];
+        let activities = vec![
+            activity(1, 3, vec![]),
+            activity(2, 1, link(LinkType::StartToStart, 1)),
+            activity(3, 1, link(LinkType::FinishToFinish, 0)),
+            activity(4, 2, link(LinkType::StartToFinish, 3)),
+            activity(5, 1, link(LinkType::FinishToStart, -1)),
+            activity(6, 1, link(LinkType::FinishToStart, 2)),
+        ];
+        let scheduled = compute(&activities, None, &Calendar::standard(&[])).unwrap();
+
+        assert_eq!(task(&scheduled, 2).early_start, date(20));
+        assert_eq!(task(&scheduled, 3).early_start, date(21));
+        assert_eq!(task(&scheduled, 3).early_finish, date(21));
+        assert_eq!(task(&scheduled, 4).early_start, date(20));
+        assert_eq!(task(&scheduled, 4).early_finish, date(21));
+        assert_eq!(task(&scheduled, 5).early_start, date(21));
+        assert_eq!(task(&scheduled, 6).early_start, date(26));
+    }
+
+    #[test]
+    fn lead_time_never_starts_before_the_project() {
+        let activities = vec![
+            activity(1, 2, vec![]),
+            activity(2, 4, vec![Link::new(1, LinkType::FinishToFinish, 0)]),
+        ];
+        let scheduled = compute(&activities, None, &Calendar::standard(&[])).unwrap();
+
+        assert_eq!(task(&scheduled, 2).early_start, date(19));
+        assert_eq!(task(&scheduled, 2).early_finish, date(22));
+    }
+
+    #[test]
+    fn backward_pass_follows_typed_links() {
+        let activities = vec![
+            activity(1, 3, vec![]),
+            activity(2, 1, vec![Link::new(1, LinkType::StartToStart, 1)]),
+            activity(3, 3, vec![Link::new(2, LinkType::FinishToFinish, 2)]),
+        ];
+        let scheduled = compute(&activities, None, &Calendar::standard(&[])).unwrap();
+
+        // Task 3 finishes two days after task 2, after task 1, so nothing can slip
+        assert_eq!(task(&scheduled, 3).early_finish, date(22));
+        assert_eq!(task(&scheduled, 3).total_slack, 0);
+        assert_eq!(task(&scheduled, 2).total_slack, 0);
+        assert_eq!(task(&scheduled, 2).late_finish, date(20));
+        // Starting task 1 later would start task 2 later
+        assert_eq!(task(&scheduled, 1).total_slack, 0);
+        assert_eq!(task(&scheduled, 1).free_slack, 0);
+    }
+
+    #[test]
+    fn leveling_delays_tasks_by_working_days() {
+        let calendar = Calendar::standard(&[]);
+        let mut first = activity(1, 2, vec![]);
+        first.delay(3, &calendar);
+        let mut second = activity(2, 1, vec![after(1)]);
+        second.delay(2, &calendar);
+        assert_eq!(first.start_date, date(22));
+        assert_eq!(second.leveling_delay, 2);
+
+        let scheduled = compute(&[first, second], None, &calendar).unwrap();
+        assert_eq!(task(&scheduled, 1).early_start, date(22));
+        assert_eq!(task(&scheduled, 2).early_start, date(28));
+    }
+
+    #[test]
+    fn refuses_unknown_predecessors() {
+        let activities = vec![activity(1, 1, vec![]), activity(2, 1, vec![after(9)])];
+        let error = compute(&activities, None, &Calendar::standard(&[]))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Task #2 Task 2 has an unknown predecessor #9"
+        );
+    }
+
+    #[test]
+    fn refuses_circular_links() {
+        let activities = vec![
+            activity(1, 1, vec![after(2)]),
+            activity(2, 1, vec![after(1)]),
+        ];
+        let error = compute(&activities, None, &Calendar::standard(&[]))
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "Dependency cycle between tasks #1, #2");
+    }
+}
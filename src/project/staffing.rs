@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Local, NaiveDate};
+use plotly::color::NamedColor::Red;
+use plotly::common::{DashType, Mode, Title};
+use plotly::layout::Axis;
+use plotly::{common, Layout, Plot, Scatter};
+use sqlx::{Pool, Sqlite};
+
+use crate::project::calendar::{self, Calendar};
+use crate::project::chart::{
+    export_chart, generate_week_numbers, week_labels, week_number, ChartOutput,
+};
+use crate::project::team;
+
+// Planned headcount per week and role, next to the staffing booked in the timesheets
+pub(crate) async fn generate_chart(
+    pool: &Pool<Sqlite>,
+    project_id: i64,
+    chart_title: &str,
+    output: &ChartOutput,
+) -> anyhow::Result<()> {
+    let tasks = sqlx::query!(
+        r#"
+        SELECT start_date, finish_date, resource_names
+        FROM tasks
+        WHERE project_id = ?1
+          AND duration > 0
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let timesheet = sqlx::query!(
+        r#"
+        SELECT ts.date as "date!", ts.duration as "days!"
+        FROM timesheet ts
+        WHERE ts.project_id = ?1
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let calendar = calendar::project_calendar(pool, project_id).await?;
+
+    let mut planned = vec![];
+    for task in tasks {
+        let start_date: NaiveDate = task.start_date.parse()?;
+        let finish_date: NaiveDate = task.finish_date.parse()?;
+        let resource_names = task.resource_names.unwrap_or_default();
+        let roles: Vec<String> = resource_names
+            .split(',')
+            .map(|resource| team::role_of(resource).to_uppercase())
+            .filter(|role| !role.is_empty())
+            .collect();
+        planned.push((start_date, finish_date, roles));
+    }
+    let mut actual = vec![];
+    for entry in timesheet {
+        actual.push((entry.date.parse::<NaiveDate>()?, entry.days as f64));
+    }
+
+    let Some(from_date) = planned.iter().map(|(start, _, _)| *start).min() else {
+        anyhow::bail!("No tasks to chart, import a plan first");
+    };
+    let to_date = planned
+        .iter()
+        .map(|(_, finish, _)| *finish)
+        .chain(actual.iter().map(|(date, _)| *date))
+        .max()
+        .unwrap_or(from_date)
+        .max(Local::now().date_naive());
+    let week_numbers = generate_week_numbers(from_date, to_date);
+
+    let planned_staffing = planned_headcount(&planned, &week_numbers, &calendar);
+    let actual_staffing = actual_headcount(&actual, &week_numbers, &calendar);
+    let current_week = week_number(Local::now().date_naive());
+    let actual_staffing: Vec<f64> = actual_staffing
+        .into_iter()
+        .zip(&week_numbers)
+        .take_while(|(_, &week)| week <= current_week)
+        .map(|(people, _)| people)
+        .collect();
+
+    let x_axis = week_labels(&week_numbers);
+    let total: Vec<f64> = (0..week_numbers.len())
+        .map(|i| planned_staffing.values().map(|values| values[i]).sum())
+        .collect();
+    if let Some((peak_week, peak)) = total.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1)) {
+        println!(
+            "👥 Planned staffing peaks at {:.1} people in {}",
+            peak, x_axis[peak_week]
+        );
+    }
+
+    let mut plot = Plot::new();
+    for (role, values) in planned_staffing {
+        let trace = Scatter::new(x_axis.clone(), values)
+            .mode(Mode::Lines)
+            .stack_group("planned")
+            .name(format!("Planned {}", role).as_str());
+        plot.add_trace(trace);
+    }
+    let trace = Scatter::new(x_axis[..actual_staffing.len()].to_vec(), actual_staffing)
+        .mode(Mode::Lines)
+        .line(common::Line::new().color(Red).dash(DashType::Dash))
+        .name("Actual staffing 🧑‍💻");
+    plot.add_trace(trace);
+
+    let layout = Layout::new()
+        .title(Title::new(chart_title))
+        .x_axis(Axis::new().title(Title::from("Week #")))
+        .y_axis(Axis::new().title(Title::from("People")));
+    plot.set_layout(layout);
+
+    export_chart(&plot, output, "staffing_chart", "Staffing Chart")
+}
+
+// Every planned resource is one person for each workday of the task
+fn planned_headcount(
+    planned: &[(NaiveDate, NaiveDate, Vec<String>)],
+    week_numbers: &[i32],
+    calendar: &Calendar,
+) -> BTreeMap<String, Vec<f64>> {
+    let workdays_per_week = calendar.workdays_per_week() as f64;
+    let mut planned_staffing: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for (start_date, finish_date, roles) in planned {
+        let mut date = *start_date;
+        while date <= *finish_date {
+            if calendar.is_workday(date) {
+                if let Some(index) = week_numbers.iter().position(|&x| x == week_number(date)) {
+                    for role in roles {
+                        planned_staffing
+                            .entry(role.clone())
+                            .or_insert_with(|| vec![0.0; week_numbers.len()])[index] +=
+                            1.0 / workdays_per_week;
+                    }
+                }
+            }
+            date += Duration::days(1);
+        }
+    }
+    planned_staffing
+}
+
+// Days logged in a week, as people working full time that week
+fn actual_headcount(
+    actual: &[(NaiveDate, f64)],
+    week_numbers: &[i32],
+    calendar: &Calendar,
+) -> Vec<f64> {
+    let workdays_per_week = calendar.workdays_per_week() as f64;
+    let mut actual_staffing = vec![0.0; week_numbers.len()];
+    for (date, days) in actual {
+        if let Some(index) = week_numbers.iter().position(|&x| x == week_number(*date)) {
+            actual_staffing[index] += days / workdays_per_week;
+        }
+    }
+    actual_staffing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{test_pool, TEST_PROJECT};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn roles(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn counts_planned_people_per_role_and_week() {
+        let weeks = generate_week_numbers(date(19), date(30));
+        let planned = vec![
+            // A full week for two people, then Mon to Wed of the next week
+            (date(19), date(28), roles(&["ARC", "DEV"])),
+            // Thu to Tue, the weekend is not worked
+            (date(22), date(27), roles(&["DEV"])),
+        ];
+
+        let headcount = planned_headcount(&planned, &weeks, &Calendar::standard(&[]));
+        assert_eq!(headcount.keys().collect::<Vec<_>>(), vec!["ARC", "DEV"]);
+        let rounded = |values: &Vec<f64>| {
+            values
+                .iter()
+                .map(|value| (value * 10.0).round() / 10.0)
+                .collect::<Vec<f64>>()
+        };
+        assert_eq!(rounded(&headcount["ARC"]), vec![1.0, 0.6]);
+        assert_eq!(rounded(&headcount["DEV"]), vec![1.4, 1.0]);
+    }
+
+    #[test]
+    fn counts_logged_days_as_people() {
+        let weeks = generate_week_numbers(date(19), date(30));
+        // Days logged outside the chart are left out
+        let actual = vec![
+            (date(5), 5.0),
+            (date(19), 5.0),
+            (date(23), 5.0),
+            (date(26), 2.0),
+        ];
+        assert_eq!(
+            actual_headcount(&actual, &weeks, &Calendar::standard(&[])),
+            vec![2.0, 0.4]
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_project_has_no_chart() {
+        let pool = test_pool().await;
+        let output = ChartOutput {
+            format: None,
+            out_file: None,
+            open: false,
+            settings: Default::default(),
+        };
+
+        let error = generate_chart(&pool, TEST_PROJECT, "Empty", &output)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "No tasks to chart, import a plan first");
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+
+use colored::Colorize;
+use prettytable::{row, Table};
+use promptly::prompt_default;
+use sqlx::SqlitePool;
+
+use crate::config::{Config, DateFormats};
+use crate::project::{
+    allocation, dependency, dfmt, import_plan_calendar, insert_task, load_plan, schedule, validate,
+    MsProjectTask,
+};
+
+// Task as currently stored, with the progress that must survive a re-import. Dates and slack
+// are left out, scheduling owns them once the plan is imported.
+struct StoredTask {
+    id: i64,
+    name: String,
+    duration: i64,
+    predecessors: String,
+    resource_names: String,
+    logged: i64,
+    has_task_data: bool,
+}
+
+enum PlanChange<'a> {
+    Added(&'a MsProjectTask),
+    Changed(&'a MsProjectTask, Vec<String>),
+    Removed(&'a StoredTask),
+    // Removed from the plan, but assignments or logged work keep it in the database
+    Kept(&'a StoredTask),
+}
+
+// Merge an updated MS Project plan into an existing database, keeping assignments,
+// completions and timesheets
+pub(crate) async fn merge_plan(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    ms_project_file: String,
+    yes: bool,
+) -> anyhow::Result<()> {
+    let (tasks, problems) = load_plan(&ms_project_file)
+        .map_err(|e| anyhow::anyhow!("Failed to load tasks from {}: {}", ms_project_file, e))?;
+    validate::check_import(&ms_project_file, &tasks, problems)?;
+    let stored = load_stored_tasks(pool, project_id).await?;
+
+    let changes = diff(&tasks, &stored);
+    if changes.is_empty() {
+        println!("✨Plan is unchanged, nothing to merge");
+        allocation::reset_leveling(&mut *pool.acquire().await?, project_id).await?;
+        // The calendar may still have changed
+        import_plan_calendar(pool, project_id, &ms_project_file).await?;
+        return schedule::update(pool, project_id, config).await;
+    }
+    print_changes(&changes, &config.dates);
+
+    if !yes {
+        let confirm = prompt_default("💾 Apply these changes?", false);
+        if confirm.is_err() || !confirm.unwrap() {
+            println!("👋 Nothing merged, bye!");
+            return Ok(());
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+    for change in &changes {
+        match change {
+            PlanChange::Added(task) => {
+                insert_task(&mut tx, project_id, task).await?;
+            }
+            PlanChange::Changed(task, _) => {
+                sqlx::query!(
+                    r#"
+                    UPDATE tasks
+                    SET name             = ?1,
+                        duration         = ?2,
+                        resource_names   = ?3,
+                        pdex_criticality = ?4
+                    WHERE project_id = ?6
+                      AND id = ?5
+                    "#,
+                    task.name,
+                    task.duration,
+                    task.resource_names,
+                    task.pdex_criticality,
+                    task.id,
+                    project_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            PlanChange::Removed(task) => {
+                dependency::remove_task(&mut tx, project_id, task.id).await?;
+                sqlx::query!(
+                    r#"DELETE FROM tasks WHERE project_id = ?1 AND id = ?2"#,
+                    project_id,
+                    task.id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            PlanChange::Kept(_) => {}
+        }
+    }
+    allocation::reset_leveling(&mut tx, project_id).await?;
+    // Links are stored once all tasks they refer to are
+    for change in &changes {
+        if let PlanChange::Added(task) | PlanChange::Changed(task, _) = change {
+            dependency::save(&mut tx, project_id, task.id as i64, &task.links()).await?;
+        }
+    }
+    tx.commit().await?;
+
+    let merged = changes
+        .iter()
+        .filter(|change| !matches!(change, PlanChange::Kept(_)))
+        .count();
+    println!("✨Merged {} changes into the plan", merged);
+    import_plan_calendar(pool, project_id, &ms_project_file).await?;
+    // The dates and slack in the file do not know about the logged and completed work
+    schedule::update(pool, project_id, config).await?;
+    Ok(())
+}
+
+async fn load_stored_tasks(pool: &SqlitePool, project_id: i64) -> anyhow::Result<Vec<StoredTask>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.id             as "id!",
+               t.name           as name,
+               t.duration       as duration,
+               t.resource_names as resource_names,
+               (SELECT sum(ts.duration)
+                FROM timesheet ts
+                WHERE ts.project_id = t.project_id
+                  AND ts.task_id = t.id) as "logged: i64",
+               (SELECT count(*)
+                FROM task_data td
+                WHERE td.project_id = t.project_id
+                  AND td.task_id = t.id) as "task_data_count!: i64"
+        FROM tasks t
+        WHERE t.project_id = ?1
+        ORDER BY t.id
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let links = dependency::load(pool, project_id).await?;
+    let mut tasks = vec![];
+    for row in rows {
+        tasks.push(StoredTask {
+            id: row.id,
+            name: row.name,
+            duration: row.duration,
+            predecessors: links
+                .get(&row.id)
+                .map(|links| dependency::format_links(links))
+                .unwrap_or_default(),
+            resource_names: row.resource_names.unwrap_or_default(),
+            logged: row.logged.unwrap_or(0),
+            has_task_data: row.task_data_count > 0,
+        });
+    }
+    Ok(tasks)
+}
+
+fn diff<'a>(tasks: &'a [MsProjectTask], stored: &'a [StoredTask]) -> Vec<PlanChange<'a>> {
+    let stored_by_id: HashMap<i64, &StoredTask> =
+        stored.iter().map(|task| (task.id, task)).collect();
+
+    let mut changes = vec![];
+    for task in tasks {
+        match stored_by_id.get(&(task.id as i64)) {
+            None => changes.push(PlanChange::Added(task)),
+            Some(existing) => {
+                let mut fields = vec![];
+                if existing.name != task.name {
+                    fields.push(format!("name: {} → {}", existing.name, task.name));
+                }
+                if existing.duration != task.duration as i64 {
+                    fields.push(format!(
+                        "duration: {} → {}",
+                        existing.duration, task.duration
+                    ));
+                }
+                let predecessors = dependency::format_links(&task.links());
+                if existing.predecessors != predecessors {
+                    fields.push(format!(
+                        "predecessors: {} → {}",
+                        existing.predecessors, predecessors
+                    ));
+                }
+                if existing.resource_names != task.resource_names {
+                    fields.push(format!(
+                        "resources: {} → {}",
+                        existing.resource_names, task.resource_names
+                    ));
+                }
+                if !fields.is_empty() {
+                    changes.push(PlanChange::Changed(task, fields));
+                }
+            }
+        }
+    }
+
+    for existing in stored {
+        if !tasks.iter().any(|task| task.id as i64 == existing.id) {
+            if existing.logged > 0 || existing.has_task_data {
+                changes.push(PlanChange::Kept(existing));
+            } else {
+                changes.push(PlanChange::Removed(existing));
+            }
+        }
+    }
+    changes
+}
+
+fn print_changes(changes: &[PlanChange], dates: &DateFormats) {
+    let mut table = Table::new();
+    table.add_row(row![
+        "Change".bold(),
+        "#".bold(),
+        "Task".bold(),
+        "Details".bold()
+    ]);
+    for change in changes {
+        match change {
+            PlanChange::Added(task) => table.add_row(row![
+                "added".green(),
+                task.id.to_string(),
+                task.name,
+                format!(
+                    "{} days, {} → {}",
+                    task.duration,
+                    dfmt(task.start_date, dates),
+                    dfmt(task.finish_date, dates)
+                )
+            ]),
+            PlanChange::Changed(task, fields) => table.add_row(row![
+                "changed".yellow(),
+                task.id.to_string(),
+                task.name,
+                fields.join("\n")
+            ]),
+            PlanChange::Removed(task) => {
+                table.add_row(row!["removed".red(), task.id.to_string(), task.name, "--"])
+            }
+            PlanChange::Kept(task) => table.add_row(row![
+                "⚠️ removed".red().bold(),
+                task.id.to_string(),
+                task.name,
+                format!("Not in the new plan but {}, kept", kept_reason(task))
+            ]),
+        };
+    }
+    table.printstd();
+}
+
+// Why a task that left the plan stays in the database
+fn kept_reason(task: &StoredTask) -> String {
+    match (task.logged > 0, task.has_task_data) {
+        (true, true) => format!("has {} days logged and is assigned", task.logged),
+        (true, false) => format!("has {} days logged", task.logged),
+        _ => "is assigned".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::project::{import, test_pool, TEST_PROJECT};
+
+    fn task(id: i32, duration: i32, predecessors: &str) -> MsProjectTask {
+        MsProjectTask {
+            id,
+            name: format!("Task {}", id),
+            duration,
+            predecessors: predecessors.to_string(),
+            start_date: NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+            finish_date: NaiveDate::from_ymd_opt(2026, 10, 23).unwrap(),
+            total_slack: 0,
+            resource_names: "DEV1".to_string(),
+            pdex_criticality: None,
+            line: None,
+        }
+    }
+
+    fn stored(id: i64, duration: i64, predecessors: &str) -> StoredTask {
+        StoredTask {
+            id,
+            name: format!("Task {}", id),
+            duration,
+            predecessors: predecessors.to_string(),
+            resource_names: "DEV1".to_string(),
+            logged: 0,
+            has_task_data: false,
+        }
+    }
+
+    fn summary(changes: &[PlanChange]) -> Vec<(&'static str, i64, Vec<String>)> {
+        changes
+            .iter()
+            .map(|change| match change {
+                PlanChange::Added(task) => ("added", task.id as i64, vec![]),
+                PlanChange::Changed(task, fields) => ("changed", task.id as i64, fields.clone()),
+                PlanChange::Removed(task) => ("removed", task.id, vec![]),
+                PlanChange::Kept(task) => ("kept", task.id, vec![]),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unchanged_plan_has_no_changes() {
+        let tasks = vec![task(1, 5, ""), task(2, 3, "1")];
+        let stored = vec![stored(1, 5, ""), stored(2, 3, "1")];
+        assert!(diff(&tasks, &stored).is_empty());
+    }
+
+    #[test]
+    fn dates_and_slack_are_not_plan_changes() {
+        // Scheduling moved the stored dates, the file still has the original ones
+        let mut moved = task(1, 5, "");
+        moved.start_date = NaiveDate::from_ymd_opt(2026, 11, 2).unwrap();
+        moved.finish_date = NaiveDate::from_ymd_opt(2026, 11, 6).unwrap();
+        moved.total_slack = 12;
+        assert!(diff(&[moved], &[stored(1, 5, "")]).is_empty());
+    }
+
+    #[test]
+    fn lists_the_changed_fields() {
+        let mut renamed = task(2, 4, "1,3");
+        renamed.name = "Review".to_string();
+        renamed.resource_names = "DEV2".to_string();
+        let tasks = vec![task(1, 5, ""), renamed];
+        let stored = vec![stored(1, 5, ""), stored(2, 3, "1")];
+
+        assert_eq!(
+            summary(&diff(&tasks, &stored)),
+            vec![(
+                "changed",
+                2,
+                vec![
+                    "name: Task 2 → Review".to_string(),
+                    "duration: 3 → 4".to_string(),
+                    "predecessors: 1 → 1,3".to_string(),
+                    "resources: DEV1 → DEV2".to_string(),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn keeps_removed_tasks_with_progress() {
+        let tasks = vec![task(1, 5, ""), task(4, 2, "1")];
+        let mut logged = stored(2, 3, "1");
+        logged.logged = 2;
+        let mut assigned = stored(3, 3, "1");
+        assigned.has_task_data = true;
+        let stored = vec![stored(1, 5, ""), logged, assigned, stored(5, 1, "")];
+
+        assert_eq!(
+            summary(&diff(&tasks, &stored)),
+            vec![
+                ("added", 4, vec![]),
+                ("kept", 2, vec![]),
+                ("kept", 3, vec![]),
+                ("removed", 5, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn tells_why_a_task_is_kept() {
+        let mut task = stored(2, 3, "");
+        task.has_task_data = true;
+        assert_eq!(kept_reason(&task), "is assigned");
+        task.logged = 2;
+        assert_eq!(kept_reason(&task), "has 2 days logged and is assigned");
+        task.has_task_data = false;
+        assert_eq!(kept_reason(&task), "has 2 days logged");
+    }
+
+    #[tokio::test]
+    async fn syncing_drops_the_leveling_delays() {
+        let pool = test_pool().await;
+        let plan = "examples/tasks-example.xml".to_string();
+        import(&pool, TEST_PROJECT, plan.clone(), ":memory:".to_string())
+            .await
+            .unwrap();
+        sqlx::query!(r#"UPDATE tasks SET leveling_delay = 3"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        merge_plan(&pool, TEST_PROJECT, &Config::default(), plan, true)
+            .await
+            .unwrap();
+        let delayed = sqlx::query!(
+            r#"SELECT count(*) as "count!: i64" FROM tasks WHERE leveling_delay != 0"#
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(delayed.count, 0);
+    }
+}
@@ -0,0 +1,563 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+use colored::Colorize;
+use prettytable::{row, Table};
+use promptly::prompt_default;
+use sqlx::SqlitePool;
+
+use crate::config::TeamMember;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Member {
+    pub(crate) id: i64,
+    pub(crate) name: String,
+    pub(crate) role: Option<String>,
+    pub(crate) capacity: f64,
+    pub(crate) cost_rate: f64,
+}
+
+pub(crate) async fn add(
+    pool: &SqlitePool,
+    name: &str,
+    role: &Option<String>,
+    capacity: f64,
+    cost_rate: f64,
+) -> anyhow::Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("Team member needs a name");
+    }
+    if capacity <= 0.0 {
+        bail!("Capacity must be more than 0 (1.0 is full time)");
+    }
+    // Roles use the MS Project resource codes, e.g. ARC, DEV, UX
+    let role = role.as_ref().map(|role| role.trim().to_uppercase());
+
+    let existing = sqlx::query!(
+        r#"SELECT id as "id!", active as "active!: bool" FROM team WHERE name = ?1"#,
+        name
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let id = match existing {
+        Some(member) if member.active => bail!("{} is already in the team", name),
+        // Someone who left the team and rejoins keeps their history
+        Some(member) => {
+            sqlx::query!(
+                r#"
+                UPDATE team
+                SET role = ?1, capacity = ?2, cost_rate = ?3, active = 1
+                WHERE id = ?4
+                "#,
+                role,
+                capacity,
+                cost_rate,
+                member.id
+            )
+            .execute(pool)
+            .await?;
+            member.id
+        }
+        None => sqlx::query!(
+            r#"INSERT INTO team (name, role, capacity, cost_rate) VALUES (?1, ?2, ?3, ?4)"#,
+            name,
+            role,
+            capacity,
+            cost_rate
+        )
+        .execute(pool)
+        .await?
+        .last_insert_rowid(),
+    };
+
+    println!(
+        "🧑‍💻 Added {} ({}) to the team (id: {})",
+        name,
+        role.unwrap_or("no role".to_string()),
+        id
+    );
+    Ok(())
+}
+
+// Add the team members of the config that are not in the database yet, members who left
+// the team stay away until added again
+pub(crate) async fn add_configured(pool: &SqlitePool, team: &[TeamMember]) -> anyhow::Result<()> {
+    if team.is_empty() {
+        return Ok(());
+    }
+    // Names are compared like the team table does, without case
+    let known: Vec<String> = sqlx::query!(r#"SELECT name FROM team"#)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|member| member.name.to_lowercase())
+        .collect();
+    for member in team {
+        if !known.contains(&member.name.trim().to_lowercase()) {
+            add(
+                pool,
+                &member.name,
+                &member.role,
+                member.capacity,
+                member.cost_rate,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn remove(pool: &SqlitePool, member: &str, yes: bool) -> anyhow::Result<()> {
+    let member = find(pool, member).await?;
+
+    let assignments = sqlx::query!(
+        r#"
+        SELECT count(*)                                                as "tasks!: i64",
+               sum(CASE WHEN finished_at IS NULL THEN 1 ELSE 0 END) as "open: i64",
+               (SELECT count(*) FROM timesheet ts WHERE ts.member_id = ?1) as "logged!: i64"
+        FROM task_data
+        WHERE member_id = ?1
+        "#,
+        member.id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let open = assignments.open.unwrap_or(0);
+    if open > 0 {
+        bail!(
+            "{} is assigned to {} open tasks, reassign them first",
+            member.name,
+            open
+        );
+    }
+
+    if !yes {
+        let confirm = prompt_default(format!("🗑️ Remove {} from the team?", member.name), false);
+        if confirm.is_err() || !confirm.unwrap() {
+            println!("👋 Nothing removed, bye!");
+            return Ok(());
+        }
+    }
+
+    sqlx::query!(
+        r#"DELETE FROM resource_mappings WHERE member_id = ?1"#,
+        member.id
+    )
+    .execute(pool)
+    .await?;
+
+    // Completed tasks and logged work keep referring to the member, so only hide them
+    if assignments.tasks > 0 || assignments.logged > 0 {
+        sqlx::query!(r#"UPDATE team SET active = 0 WHERE id = ?1"#, member.id)
+            .execute(pool)
+            .await?;
+    } else {
+        sqlx::query!(r#"DELETE FROM team WHERE id = ?1"#, member.id)
+            .execute(pool)
+            .await?;
+    }
+
+    println!("👋 Removed {} from the team", member.name);
+    Ok(())
+}
+
+pub(crate) async fn list(pool: &SqlitePool, project_id: i64) -> anyhow::Result<()> {
+    let members = members(pool).await?;
+    let mappings = mappings(pool, project_id).await?;
+    if members.is_empty() {
+        println!("🧑‍💻 No team members yet, add them with `team add`");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "Id".bold(),
+        "Name".bold(),
+        "Role".bold(),
+        "Capacity".bold(),
+        "Cost Rate".bold(),
+        "Resources".bold()
+    ]);
+    for member in members {
+        let mut resources: Vec<&str> = mappings
+            .iter()
+            .filter(|(_, member_id)| **member_id == member.id)
+            .map(|(resource, _)| resource.as_str())
+            .collect();
+        resources.sort();
+        table.add_row(row![
+            member.id.to_string().dimmed(),
+            member.name,
+            member.role.unwrap_or("--".to_string()),
+            format!("{:.0} %", member.capacity * 100.0),
+            format!("{:.2} / day", member.cost_rate),
+            resources.join(",")
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+// Active team members, ordered by name
+pub(crate) async fn members(pool: &SqlitePool) -> anyhow::Result<Vec<Member>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id as "id!", name, role, capacity, cost_rate
+        FROM team
+        WHERE active = 1
+        ORDER BY name
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Member {
+            id: row.id,
+            name: row.name,
+            role: row.role,
+            capacity: row.capacity,
+            cost_rate: row.cost_rate,
+        })
+        .collect())
+}
+
+// Active member logging work under this name, people outside the team have no ID
+pub(crate) async fn member_id(
+    pool: &SqlitePool,
+    person: Option<&str>,
+) -> anyhow::Result<Option<i64>> {
+    let Some(person) = person else {
+        return Ok(None);
+    };
+    Ok(members(pool)
+        .await?
+        .into_iter()
+        .find(|m| m.name.eq_ignore_ascii_case(person.trim()))
+        .map(|m| m.id))
+}
+
+// Looks up an active member by ID or name
+pub(crate) async fn find(pool: &SqlitePool, member: &str) -> anyhow::Result<Member> {
+    let member = member.trim();
+    let id = member.parse::<i64>().ok();
+    members(pool)
+        .await?
+        .into_iter()
+        .find(|m| Some(m.id) == id || m.name.eq_ignore_ascii_case(member))
+        .ok_or_else(|| anyhow::anyhow!("No team member {}, add them with `team add`", member))
+}
+
+// Planned resources of the current project mapped to the member filling them, keyed on the
+// upper case resource name
+pub(crate) async fn mappings(
+    pool: &SqlitePool,
+    project_id: i64,
+) -> anyhow::Result<HashMap<String, i64>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT rm.resource_name as "resource_name!", rm.member_id as "member_id!"
+        FROM resource_mappings rm
+                 JOIN team tm ON tm.id = rm.member_id
+        WHERE rm.project_id = ?1
+          AND tm.active = 1
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.resource_name.to_uppercase(), row.member_id))
+        .collect())
+}
+
+// Whether the member's role is one of the planned resources, e.g. DEV for DEV3.
+// Without a role or planned resources there is nothing to check
+pub(crate) fn has_role_for(member: &Member, resource_names: &[String]) -> bool {
+    let Some(role) = &member.role else {
+        return true;
+    };
+    let roles: Vec<&str> = resource_names
+        .iter()
+        .map(|resource| role_of(resource))
+        .filter(|resource| !resource.is_empty())
+        .collect();
+    roles.is_empty()
+        || roles
+            .iter()
+            .any(|resource| resource.eq_ignore_ascii_case(role))
+}
+
+// Role of a planned resource, numbered resources like DEV3 share the role DEV
+pub(crate) fn role_of(resource: &str) -> &str {
+    resource
+        .trim()
+        .trim_end_matches(|c: char| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::project::{assign_tasks, test_pool, TEST_PROJECT};
+
+    fn member(role: Option<&str>) -> Member {
+        Member {
+            id: 1,
+            name: "Dana".to_string(),
+            role: role.map(str::to_string),
+            capacity: 1.0,
+            cost_rate: 0.0,
+        }
+    }
+
+    fn resources(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn numbered_resources_share_a_role() {
+        assert_eq!(role_of("DEV3"), "DEV");
+        assert_eq!(role_of(" ARC "), "ARC");
+        assert_eq!(role_of("UX12"), "UX");
+        assert_eq!(role_of("42"), "");
+    }
+
+    #[test]
+    fn members_fill_resources_of_their_role() {
+        let developer = member(Some("DEV"));
+        assert!(has_role_for(&developer, &resources(&["dev2"])));
+        assert!(has_role_for(&developer, &resources(&["ARC1", "DEV1"])));
+        assert!(!has_role_for(&developer, &resources(&["ARC1"])));
+        // Nothing to check without a role or planned resources
+        assert!(has_role_for(&developer, &resources(&[])));
+        assert!(has_role_for(&member(None), &resources(&["ARC1"])));
+    }
+
+    #[tokio::test]
+    async fn finds_members_by_id_or_name() {
+        let pool = test_pool().await;
+        add(&pool, " Dana ", &Some("dev".to_string()), 0.5, 400.0)
+            .await
+            .unwrap();
+
+        let dana = find(&pool, "DANA").await.unwrap();
+        assert_eq!(dana.name, "Dana");
+        assert_eq!(dana.role.as_deref(), Some("DEV"));
+        assert_eq!(dana.capacity, 0.5);
+        assert_eq!(
+            find(&pool, &dana.id.to_string()).await.unwrap().name,
+            "Dana"
+        );
+
+        let error = find(&pool, "bob").await.err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "No team member bob, add them with `team add`"
+        );
+        let error = add(&pool, "dana", &None, 1.0, 0.0).await.err().unwrap();
+        assert_eq!(error.to_string(), "dana is already in the team");
+        let error = add(&pool, "bob", &None, 0.0, 0.0).await.err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Capacity must be more than 0 (1.0 is full time)"
+        );
+    }
+
+    #[tokio::test]
+    async fn members_with_history_leave_and_rejoin_under_their_id() {
+        let pool = test_pool().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack,
+                               resource_names)
+            VALUES (1, 1, 'Start', 0, '2026-10-19', '2026-10-19', 0, ''),
+                   (1, 2, 'Design', 5, '2026-10-19', '2026-10-23', 0, 'DEV1');
+            INSERT INTO dependencies (project_id, task_id, predecessor_id) VALUES (1, 2, 1);
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        add(&pool, "dana", &Some("DEV".to_string()), 1.0, 0.0)
+            .await
+            .unwrap();
+        add(&pool, "bob", &None, 1.0, 0.0).await.unwrap();
+        let dana = find(&pool, "dana").await.unwrap();
+        assign_tasks(
+            pool.clone(),
+            TEST_PROJECT,
+            &Config::default(),
+            &[2],
+            Some(dana.id.to_string()),
+            false,
+        )
+        .await
+        .unwrap();
+
+        let error = remove(&pool, "dana", true).await.err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "dana is assigned to 1 open tasks, reassign them first"
+        );
+
+        sqlx::query!(r#"UPDATE task_data SET finished_at = '2026-10-23 17:00:00'"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+        remove(&pool, "dana", true).await.unwrap();
+        remove(&pool, "bob", true).await.unwrap();
+        assert!(members(&pool).await.unwrap().is_empty());
+
+        add(&pool, "dana", &Some("ARC".to_string()), 1.0, 0.0)
+            .await
+            .unwrap();
+        let rejoined = find(&pool, "dana").await.unwrap();
+        assert_eq!(rejoined.id, dana.id);
+        assert_eq!(rejoined.role.as_deref(), Some("ARC"));
+    }
+
+    #[tokio::test]
+    async fn members_who_logged_work_keep_their_id() {
+        let pool = test_pool().await;
+        add(&pool, "bob", &None, 1.0, 0.0).await.unwrap();
+        let bob = find(&pool, "bob").await.unwrap();
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack)
+            VALUES (1, 1, 'Design', 5, '2026-10-19', '2026-10-23', 0);
+            INSERT INTO timesheet (project_id, task_id, date, duration, person, member_id)
+            VALUES (1, 1, '2026-10-20', 1, 'bob', ?1);
+            "#,
+            bob.id
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        remove(&pool, "bob", true).await.unwrap();
+        assert!(members(&pool).await.unwrap().is_empty());
+        add(&pool, "bob", &None, 1.0, 0.0).await.unwrap();
+        assert_eq!(find(&pool, "bob").await.unwrap().id, bob.id);
+    }
+
+    #[tokio::test]
+    async fn assignments_follow_the_planned_roles() {
+        let pool = test_pool().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack,
+                               resource_names)
+            VALUES (1, 1, 'Start', 0, '2026-10-19', '2026-10-19', 0, ''),
+                   (1, 2, 'Design', 5, '2026-10-19', '2026-10-23', 0, 'ARC1');
+            INSERT INTO dependencies (project_id, task_id, predecessor_id) VALUES (1, 2, 1);
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        add(&pool, "dana", &Some("DEV".to_string()), 1.0, 0.0)
+            .await
+            .unwrap();
+
+        let error = assign_tasks(
+            pool.clone(),
+            TEST_PROJECT,
+            &Config::default(),
+            &[2],
+            Some("dana".to_string()),
+            false,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "⚠️ dana is DEV but #2 - Design is planned for ARC1, use --force to assign anyway"
+        );
+        assign_tasks(
+            pool.clone(),
+            TEST_PROJECT,
+            &Config::default(),
+            &[2],
+            Some("dana".to_string()),
+            true,
+        )
+        .await
+        .unwrap();
+
+        let assigned = sqlx::query!(r#"SELECT task_id, member_id FROM task_data"#)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(assigned.len(), 1);
+        assert_eq!(assigned[0].task_id, Some(2));
+        assert_eq!(assigned[0].member_id, Some(1));
+    }
+
+    fn configured(name: &str) -> TeamMember {
+        TeamMember {
+            name: name.to_string(),
+            role: Some("DEV".to_string()),
+            capacity: 1.0,
+            cost_rate: 0.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn configured_members_are_matched_without_case() {
+        let pool = test_pool().await;
+        add(&pool, "dana", &None, 1.0, 0.0).await.unwrap();
+
+        add_configured(&pool, &[configured("Dana "), configured("Bob")])
+            .await
+            .unwrap();
+        // Starting again adds nobody twice
+        add_configured(&pool, &[configured("Dana"), configured("BOB")])
+            .await
+            .unwrap();
+
+        let names: Vec<String> = sqlx::query!(r#"SELECT name FROM team ORDER BY id"#)
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|member| member.name)
+            .collect();
+        assert_eq!(names, vec!["dana", "Bob"]);
+    }
+
+    #[tokio::test]
+    async fn resource_mappings_belong_to_their_project() {
+        let pool = test_pool().await;
+        add(&pool, "dana", &None, 1.0, 0.0).await.unwrap();
+        add(&pool, "bob", &None, 1.0, 0.0).await.unwrap();
+        // Dana fills DEV1 in the default project, Bob in another one
+        sqlx::query!(
+            r#"
+            INSERT INTO projects (id, name) VALUES (2, 'other');
+            INSERT INTO resource_mappings (project_id, resource_name, member_id)
+            SELECT 1, 'dev1', id FROM team WHERE name = 'dana';
+            INSERT INTO resource_mappings (project_id, resource_name, member_id)
+            SELECT 2, 'DEV1', id FROM team WHERE name = 'bob';
+            INSERT INTO resource_mappings (project_id, resource_name, member_id)
+            SELECT 2, 'ARC1', id FROM team WHERE name = 'bob';
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let dana = find(&pool, "dana").await.unwrap();
+        assert_eq!(
+            mappings(&pool, TEST_PROJECT).await.unwrap(),
+            HashMap::from([("DEV1".to_string(), dana.id)])
+        );
+    }
+}
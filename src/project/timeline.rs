@@ -0,0 +1,285 @@
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use clap::ValueEnum;
+use colored::Colorize;
+use sqlx::SqlitePool;
+
+use crate::config::{Config, SlackThresholds};
+use crate::project::{calendar, dfmt, get_tasks, slack_color, Task, TaskStatus};
+
+// Width of the task column in front of the bars
+const LABEL_WIDTH: usize = 32;
+
+// How much time a single character stands for
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Zoom {
+    Day,
+    Week,
+    Month,
+}
+
+impl Zoom {
+    // Start of the period the date falls in
+    fn period_start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Zoom::Day => date,
+            Zoom::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            Zoom::Month => date.with_day(1).expect("Invalid date"),
+        }
+    }
+
+    // Column of a date when the timeline starts at `from`
+    fn column(self, from: NaiveDate, date: NaiveDate) -> i64 {
+        match self {
+            Zoom::Day => (date - from).num_days(),
+            Zoom::Week => (self.period_start(date) - from).num_days() / 7,
+            Zoom::Month => {
+                (date.year() - from.year()) as i64 * 12 + date.month() as i64 - from.month() as i64
+            }
+        }
+    }
+
+    fn column_start(self, from: NaiveDate, column: i64) -> NaiveDate {
+        match self {
+            Zoom::Day => from + Duration::days(column),
+            Zoom::Week => from + Duration::weeks(column),
+            Zoom::Month => {
+                let month = from.month0() as i64 + column;
+                NaiveDate::from_ymd_opt(
+                    from.year() + month.div_euclid(12) as i32,
+                    month.rem_euclid(12) as u32 + 1,
+                    1,
+                )
+                .expect("Invalid date")
+            }
+        }
+    }
+
+    // Label at the start of a week or month on the scale, the year is shown on the first
+    // column and in January
+    fn label(self, date: NaiveDate, first: NaiveDate) -> Option<String> {
+        match self {
+            Zoom::Day | Zoom::Week if date.weekday().num_days_from_monday() == 0 => {
+                Some(format!("W{:02}", date.iso_week().week()))
+            }
+            Zoom::Month if date.month() == 1 || date == first => {
+                Some(date.format("%b %y").to_string())
+            }
+            Zoom::Month => Some(date.format("%b").to_string()),
+            _ => None,
+        }
+    }
+}
+
+// Draw the tasks as bars on a week scale, optionally only the coming weeks
+pub(crate) async fn show(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    task_status: TaskStatus,
+    zoom: Zoom,
+    weeks: Option<u32>,
+) -> anyhow::Result<()> {
+    let mut tasks = get_tasks(pool.clone(), project_id, task_status).await?;
+    let calendar = calendar::project_calendar(pool, project_id).await?;
+    let today = Local::now().date_naive();
+
+    // The next weeks start on the Monday of the current week
+    let (from, to) = match weeks {
+        Some(weeks) => {
+            let from = Zoom::Week.period_start(today);
+            (
+                from,
+                from + Duration::weeks(weeks as i64) - Duration::days(1),
+            )
+        }
+        None => {
+            let Some(from) = tasks.iter().map(|task| task.start_date).min() else {
+                println!("📅 No tasks to show");
+                return Ok(());
+            };
+            let to = tasks
+                .iter()
+                .map(|task| task.finish_date)
+                .max()
+                .unwrap_or(from);
+            (from, to)
+        }
+    };
+    tasks.retain(|task| task.start_date <= to && task.finish_date >= from);
+    if tasks.is_empty() {
+        println!(
+            "📅 No tasks between {} and {}",
+            dfmt(from, &config.dates),
+            dfmt(to, &config.dates)
+        );
+        return Ok(());
+    }
+    tasks.sort_by_key(|task| (task.start_date, task.id));
+
+    let from = zoom.period_start(from);
+    let columns = zoom.column(from, to) + 1;
+    let today_column = zoom.column(from, today);
+
+    // Week or month labels, skipping those that would overlap the previous one
+    let mut labels = String::new();
+    let mut ticks = String::new();
+    for column in 0..columns {
+        let date = zoom.column_start(from, column);
+        let label = zoom
+            .label(date, from)
+            .filter(|_| labels.is_empty() || labels.chars().count() < column as usize);
+        let tick = match &label {
+            Some(label) => {
+                labels.push_str(&" ".repeat(column as usize - labels.chars().count()));
+                labels.push_str(label);
+                '┬'
+            }
+            None => '─',
+        };
+        if column == today_column {
+            ticks.push_str(&"┃".bold().to_string());
+        } else if matches!(zoom, Zoom::Day) && !calendar.is_workday(date) {
+            ticks.push_str(&tick.to_string().dimmed().to_string());
+        } else {
+            ticks.push(tick);
+        }
+    }
+    println!("{:LABEL_WIDTH$} {}", "", labels);
+    println!("{:LABEL_WIDTH$} {}", "", ticks);
+
+    for task in &tasks {
+        println!(
+            "{} {}",
+            label(task),
+            bar(
+                task,
+                zoom,
+                from,
+                columns,
+                today_column,
+                &calendar,
+                &config.slack,
+            )
+        );
+    }
+
+    println!(
+        "{:LABEL_WIDTH$} {} today is {}",
+        "",
+        "┃".bold(),
+        dfmt(today, &config.dates)
+    );
+    Ok(())
+}
+
+fn label(task: &Task) -> String {
+    let label = format!("#{} {}", task.id, task.name);
+    let label = if label.chars().count() > LABEL_WIDTH {
+        format!(
+            "{}…",
+            label.chars().take(LABEL_WIDTH - 1).collect::<String>()
+        )
+    } else {
+        format!("{:LABEL_WIDTH$}", label)
+    };
+    if task.finished {
+        label.dimmed().to_string()
+    } else {
+        label
+    }
+}
+
+// One character per column, days off inside a task are shaded lighter when zoomed in on days
+fn bar(
+    task: &Task,
+    zoom: Zoom,
+    from: NaiveDate,
+    columns: i64,
+    today_column: i64,
+    calendar: &calendar::Calendar,
+    thresholds: &SlackThresholds,
+) -> String {
+    let start = zoom.column(from, task.start_date);
+    let finish = zoom.column(from, task.finish_date);
+
+    let mut bar = String::new();
+    for column in 0..columns {
+        let cell = if column < start || column > finish {
+            if column == today_column {
+                "│".dimmed().to_string()
+            } else {
+                " ".to_string()
+            }
+        } else {
+            let date = zoom.column_start(from, column);
+            let block = if matches!(zoom, Zoom::Day) && !calendar.is_workday(date) {
+                "░"
+            } else {
+                "█"
+            };
+            if task.finished {
+                block.dimmed().to_string()
+            } else {
+                block.color(slack_color(task.slack, thresholds)).to_string()
+            }
+        };
+        bar.push_str(&cell);
+    }
+    bar
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn periods_start_on_monday_or_the_first() {
+        let saturday = date(2026, 10, 17);
+        assert_eq!(Zoom::Day.period_start(saturday), saturday);
+        assert_eq!(Zoom::Week.period_start(saturday), date(2026, 10, 12));
+        assert_eq!(Zoom::Month.period_start(saturday), date(2026, 10, 1));
+    }
+
+    #[test]
+    fn columns_and_their_start_dates_agree() {
+        let from = date(2026, 10, 12);
+        assert_eq!(Zoom::Day.column(from, date(2026, 10, 17)), 5);
+        assert_eq!(Zoom::Week.column(from, date(2026, 10, 18)), 0);
+        assert_eq!(Zoom::Week.column(from, date(2026, 10, 19)), 1);
+        assert_eq!(Zoom::Week.column_start(from, 2), date(2026, 10, 26));
+
+        let from = date(2026, 10, 1);
+        assert_eq!(Zoom::Month.column(from, date(2027, 2, 14)), 4);
+        assert_eq!(Zoom::Month.column_start(from, 4), date(2027, 2, 1));
+        assert_eq!(Zoom::Month.column_start(from, -10), date(2025, 12, 1));
+        // The timeline starts at the start of a period
+        for zoom in [Zoom::Day, Zoom::Week, Zoom::Month] {
+            let from = zoom.period_start(date(2026, 10, 17));
+            for column in 0..30 {
+                assert_eq!(zoom.column(from, zoom.column_start(from, column)), column);
+            }
+        }
+    }
+
+    #[test]
+    fn scale_labels_weeks_and_months() {
+        let first = date(2026, 10, 1);
+        assert_eq!(
+            Zoom::Week.label(date(2026, 10, 19), first),
+            Some("W43".to_string())
+        );
+        assert_eq!(Zoom::Day.label(date(2026, 10, 20), first), None);
+        assert_eq!(Zoom::Month.label(first, first), Some("Oct 26".to_string()));
+        assert_eq!(
+            Zoom::Month.label(date(2026, 11, 1), first),
+            Some("Nov".to_string())
+        );
+        assert_eq!(
+            Zoom::Month.label(date(2027, 1, 1), first),
+            Some("Jan 27".to_string())
+        );
+    }
+}
@@ -0,0 +1,742 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::bail;
+use chrono::{Datelike, Duration, NaiveDate};
+use colored::Colorize;
+use inquire::DateSelect;
+use prettytable::{row, Cell, Row, Table};
+use promptly::prompt_default;
+use sqlx::SqlitePool;
+use titlecase::titlecase;
+
+use crate::config::Config;
+use crate::project::{calendar, dfmt, schedule, team};
+
+struct Entry {
+    id: i64,
+    task_id: i64,
+    task_name: String,
+    person: Option<String>,
+    member_id: Option<i64>,
+    date: NaiveDate,
+    days: i64,
+}
+
+// Logged work, every filter is optional
+pub(crate) async fn list(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    task_id: Option<i64>,
+    person: &Option<String>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> anyhow::Result<()> {
+    let from = from.map(|date| date.to_string());
+    let to = to.map(|date| date.to_string());
+    let rows = sqlx::query!(
+        r#"
+        SELECT ts.id       as "id!",
+               ts.task_id  as "task_id!",
+               t.name      as "task_name?",
+               lower(coalesce(tm.name, ts.person)) as "person?: String",
+               ts.member_id as member_id,
+               ts.date     as "date!",
+               ts.duration as "days!"
+        FROM timesheet ts
+                 LEFT OUTER JOIN tasks t ON t.project_id = ts.project_id AND t.id = ts.task_id
+                 LEFT OUTER JOIN team tm ON tm.id = ts.member_id
+        WHERE ts.project_id = ?5
+          AND (?1 IS NULL OR ts.task_id = ?1)
+          AND (?2 IS NULL OR lower(coalesce(tm.name, ts.person)) = lower(?2))
+          AND (?3 IS NULL OR ts.date >= ?3)
+          AND (?4 IS NULL OR ts.date <= ?4)
+        ORDER BY ts.date, ts.id
+        "#,
+        task_id,
+        person,
+        from,
+        to,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut entries = vec![];
+    for row in rows {
+        entries.push(Entry {
+            id: row.id,
+            task_id: row.task_id,
+            task_name: row.task_name.unwrap_or("--".to_string()),
+            person: row.person,
+            member_id: row.member_id,
+            date: row.date.parse()?,
+            days: row.days,
+        });
+    }
+
+    if entries.is_empty() {
+        println!("⌛ No logged work found");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "Id".bold(),
+        "#".bold(),
+        "Task".bold(),
+        "Person".bold(),
+        "Date".bold(),
+        "Days".bold()
+    ]);
+    for entry in &entries {
+        table.add_row(row![
+            entry.id.to_string().dimmed(),
+            entry.task_id.to_string(),
+            entry.task_name,
+            titlecase(entry.person.as_deref().unwrap_or("")),
+            dfmt(entry.date, &config.dates),
+            entry.days.to_string()
+        ]);
+    }
+    table.printstd();
+    println!(
+        "⌛ {} days logged in {} entries",
+        entries.iter().map(|entry| entry.days).sum::<i64>(),
+        entries.len()
+    );
+    Ok(())
+}
+
+// New values of a timesheet entry, the ones not given are kept or prompted for
+#[derive(Default)]
+pub(crate) struct EntryChanges {
+    pub(crate) task_id: Option<i64>,
+    pub(crate) date: Option<NaiveDate>,
+    pub(crate) days: Option<i32>,
+    pub(crate) person: Option<String>,
+}
+
+// Amend a timesheet entry, prompting for the values that are not given
+pub(crate) async fn edit(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    id: i64,
+    changes: EntryChanges,
+    yes: bool,
+) -> anyhow::Result<()> {
+    let EntryChanges {
+        task_id,
+        date,
+        days,
+        person,
+    } = changes;
+    let entry = get_entry(pool, project_id, id).await?;
+    let interactive = task_id.is_none() && date.is_none() && days.is_none() && person.is_none();
+
+    let new_task_id = match task_id {
+        Some(task_id) => task_id,
+        None if interactive => prompt_default("Task", entry.task_id)?,
+        None => entry.task_id,
+    };
+    let new_date = match date {
+        Some(date) => date,
+        None if interactive => DateSelect::new("Select date: ")
+            .with_default(entry.date)
+            .prompt()?,
+        None => entry.date,
+    };
+    let new_days = match days {
+        Some(days) => days as i64,
+        None if interactive => prompt_default("Days worked", entry.days)?,
+        None => entry.days,
+    };
+    let new_person = match person {
+        Some(person) => Some(person.to_lowercase()),
+        None if interactive => {
+            let person: String =
+                prompt_default("Person", entry.person.clone().unwrap_or_default())?;
+            Some(person.to_lowercase()).filter(|person| !person.is_empty())
+        }
+        None => entry.person.clone(),
+    };
+
+    if new_days <= 0 {
+        bail!("Logged work must be at least one day, delete the entry instead");
+    }
+    // Members who left the team keep the work they logged
+    let new_member_id = if new_person == entry.person {
+        entry.member_id
+    } else {
+        team::member_id(pool, new_person.as_deref()).await?
+    };
+    // Like `log`, nobody works on weekends, holidays or their vacation. Entries that are not
+    // moved stay editable when days off are added later.
+    if new_date != entry.date || new_person != entry.person {
+        let calendar = calendar::member_calendar(pool, project_id, new_member_id).await?;
+        if !calendar.is_workday(new_date) {
+            bail!(
+                "{} is not a working day of {}",
+                dfmt(new_date, &config.dates),
+                new_person.as_deref().unwrap_or("the project")
+            );
+        }
+    }
+    if new_task_id != entry.task_id {
+        let task = sqlx::query!(
+            r#"SELECT id FROM tasks WHERE project_id = ?1 AND id = ?2"#,
+            project_id,
+            new_task_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        if task.is_none() {
+            bail!("Task #{} does not exist", new_task_id);
+        }
+    }
+
+    if new_task_id == entry.task_id
+        && new_date == entry.date
+        && new_days == entry.days
+        && new_person == entry.person
+    {
+        println!("✨Nothing changed");
+        return Ok(());
+    }
+
+    println!(
+        "✏️ Entry {}: #{} {} {} days by {} → #{} {} {} days by {}",
+        entry.id,
+        entry.task_id,
+        dfmt(entry.date, &config.dates),
+        entry.days,
+        entry.person.as_deref().unwrap_or("unknown"),
+        new_task_id,
+        dfmt(new_date, &config.dates),
+        new_days,
+        new_person.as_deref().unwrap_or("unknown")
+    );
+    if !yes {
+        let confirm = prompt_default("💾 Save the changes?", false);
+        if confirm.is_err() || !confirm.unwrap() {
+            println!("👋 Nothing changed, bye!");
+            return Ok(());
+        }
+    }
+
+    let date_string = new_date.format("%Y-%m-%d").to_string();
+    sqlx::query!(
+        r#"
+        UPDATE timesheet
+        SET task_id  = ?1,
+            date     = ?2,
+            duration = ?3,
+            person   = ?4,
+            member_id = ?5
+        WHERE id = ?6
+        "#,
+        new_task_id,
+        date_string,
+        new_days,
+        new_person,
+        new_member_id,
+        entry.id
+    )
+    .execute(pool)
+    .await?;
+
+    println!("⌛ Updated timesheet entry {}", entry.id);
+    schedule::update(pool, project_id, config).await?;
+    Ok(())
+}
+
+// Days logged per person and week
+pub(crate) async fn report(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> anyhow::Result<()> {
+    let totals = weekly_totals(pool, project_id, from, to).await?;
+    let people: BTreeSet<&String> = totals
+        .values()
+        .flat_map(|per_person| per_person.keys())
+        .collect();
+
+    if totals.is_empty() {
+        println!("⌛ No logged work found");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    let mut header = Row::new(vec![Cell::new(&"Week".bold().to_string())]);
+    for person in &people {
+        header.add_cell(Cell::new(&titlecase(person).bold().to_string()));
+    }
+    header.add_cell(Cell::new(&"Total".bold().to_string()));
+    table.add_row(header);
+
+    for (week, per_person) in &totals {
+        let mut row = Row::new(vec![Cell::new(&format!(
+            "W{:02} {}",
+            week.iso_week().week(),
+            dfmt(*week, &config.dates)
+        ))]);
+        for person in &people {
+            let days = per_person.get(*person).copied().unwrap_or(0);
+            row.add_cell(Cell::new(&days.to_string()));
+        }
+        row.add_cell(Cell::new(
+            &per_person
+                .values()
+                .sum::<i64>()
+                .to_string()
+                .bold()
+                .to_string(),
+        ));
+        table.add_row(row);
+    }
+
+    let mut total_row = Row::new(vec![Cell::new(&"Total".bold().to_string())]);
+    for person in &people {
+        let days: i64 = totals
+            .values()
+            .filter_map(|per_person| per_person.get(*person))
+            .sum();
+        total_row.add_cell(Cell::new(&days.to_string().bold().to_string()));
+    }
+    let days: i64 = totals
+        .values()
+        .flat_map(|per_person| per_person.values())
+        .sum();
+    total_row.add_cell(Cell::new(&days.to_string().bold().to_string()));
+    table.add_row(total_row);
+
+    table.printstd();
+    Ok(())
+}
+
+// Days logged per week and person
+async fn weekly_totals(
+    pool: &SqlitePool,
+    project_id: i64,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> anyhow::Result<BTreeMap<NaiveDate, BTreeMap<String, i64>>> {
+    let from = from.map(|date| date.to_string());
+    let to = to.map(|date| date.to_string());
+    let rows = sqlx::query!(
+        r#"
+        SELECT lower(coalesce(tm.name, ts.person)) as "person?: String",
+               ts.date            as "date!",
+               sum(ts.duration)   as "days!: i64"
+        FROM timesheet ts
+                 LEFT OUTER JOIN team tm ON tm.id = ts.member_id
+        WHERE ts.project_id = ?3
+          AND (?1 IS NULL OR ts.date >= ?1)
+          AND (?2 IS NULL OR ts.date <= ?2)
+        GROUP BY ts.member_id, ts.person, ts.date
+        "#,
+        from,
+        to,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // Weeks are keyed on their Monday
+    let mut totals: BTreeMap<NaiveDate, BTreeMap<String, i64>> = BTreeMap::new();
+    for row in rows {
+        let date: NaiveDate = row.date.parse()?;
+        let week = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        let person = row.person.unwrap_or("unknown".to_string());
+        *totals.entry(week).or_default().entry(person).or_default() += row.days;
+    }
+    Ok(totals)
+}
+
+pub(crate) async fn delete(
+    pool: &SqlitePool,
+    project_id: i64,
+    config: &Config,
+    id: i64,
+    yes: bool,
+) -> anyhow::Result<()> {
+    let entry = get_entry(pool, project_id, id).await?;
+
+    if !yes {
+        let confirm = prompt_default(
+            format!(
+                "🗑️ Delete {} days logged on {} for #{} - {}?",
+                entry.days,
+                dfmt(entry.date, &config.dates),
+                entry.task_id,
+                entry.task_name
+            ),
+            false,
+        );
+        if confirm.is_err() || !confirm.unwrap() {
+            println!("👋 Nothing deleted, bye!");
+            return Ok(());
+        }
+    }
+
+    sqlx::query!(r#"DELETE FROM timesheet WHERE id = ?1"#, entry.id)
+        .execute(pool)
+        .await?;
+
+    println!("🗑️ Deleted timesheet entry {}", entry.id);
+    schedule::update(pool, project_id, config).await?;
+    Ok(())
+}
+
+// Entries of other projects are not found
+async fn get_entry(pool: &SqlitePool, project_id: i64, id: i64) -> anyhow::Result<Entry> {
+    let row = sqlx::query!(
+        r#"
+        SELECT ts.id       as "id!",
+               ts.task_id  as "task_id!",
+               t.name      as "task_name?",
+               lower(coalesce(tm.name, ts.person)) as "person?: String",
+               ts.member_id as member_id,
+               ts.date     as "date!",
+               ts.duration as "days!"
+        FROM timesheet ts
+                 LEFT OUTER JOIN tasks t ON t.project_id = ts.project_id AND t.id = ts.task_id
+                 LEFT OUTER JOIN team tm ON tm.id = ts.member_id
+        WHERE ts.project_id = ?2
+          AND ts.id = ?1
+        "#,
+        id,
+        project_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    match row {
+        Some(row) => Ok(Entry {
+            id: row.id,
+            task_id: row.task_id,
+            task_name: row.task_name.unwrap_or("--".to_string()),
+            person: row.person,
+            member_id: row.member_id,
+            date: row.date.parse()?,
+            days: row.days,
+        }),
+        None => bail!("Timesheet entry {} does not exist", id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{test_pool, TEST_PROJECT};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    // Two tasks, with three days logged on Tue 20 against the first one
+    async fn pool_with_entry() -> SqlitePool {
+        let pool = test_pool().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks (project_id, id, name, duration, start_date, finish_date, total_slack)
+            VALUES (1, 1, 'Design', 5, '2026-10-19', '2026-10-23', 0),
+                   (1, 2, 'Build', 5, '2026-10-26', '2026-10-30', 0);
+            INSERT INTO dependencies (project_id, task_id, predecessor_id) VALUES (1, 2, 1);
+            INSERT INTO team (id, name) VALUES (1, 'dana');
+            INSERT INTO task_data (project_id, task_id, member_id) VALUES (1, 1, 1);
+            INSERT INTO timesheet (project_id, id, task_id, date, duration, person, member_id)
+            VALUES (1, 7, 1, '2026-10-20', 3, 'dana', 1);
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn edits_only_the_given_values() {
+        let pool = pool_with_entry().await;
+        edit(
+            &pool,
+            TEST_PROJECT,
+            &Config::default(),
+            7,
+            EntryChanges {
+                date: Some(date(21)),
+                ..Default::default()
+            },
+            true,
+        )
+        .await
+        .unwrap();
+
+        let entry = get_entry(&pool, TEST_PROJECT, 7).await.unwrap();
+        assert_eq!(entry.task_id, 1);
+        assert_eq!(entry.date, date(21));
+        assert_eq!(entry.days, 3);
+        assert_eq!(entry.person.as_deref(), Some("dana"));
+
+        edit(
+            &pool,
+            TEST_PROJECT,
+            &Config::default(),
+            7,
+            EntryChanges {
+                task_id: Some(2),
+                days: Some(1),
+                person: Some("Bob".to_string()),
+                ..Default::default()
+            },
+            true,
+        )
+        .await
+        .unwrap();
+        let entry = get_entry(&pool, TEST_PROJECT, 7).await.unwrap();
+        assert_eq!(
+            (
+                entry.task_id,
+                entry.task_name.as_str(),
+                entry.date,
+                entry.days
+            ),
+            (2, "Build", date(21), 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn refuses_edits_without_work_or_task() {
+        let pool = pool_with_entry().await;
+
+        let error = edit(
+            &pool,
+            TEST_PROJECT,
+            &Config::default(),
+            7,
+            EntryChanges {
+                days: Some(0),
+                ..Default::default()
+            },
+            true,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Logged work must be at least one day, delete the entry instead"
+        );
+        let error = edit(
+            &pool,
+            TEST_PROJECT,
+            &Config::default(),
+            7,
+            EntryChanges {
+                task_id: Some(9),
+                ..Default::default()
+            },
+            true,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(error.to_string(), "Task #9 does not exist");
+        let error = edit(
+            &pool,
+            TEST_PROJECT,
+            &Config::default(),
+            8,
+            EntryChanges {
+                task_id: Some(1),
+                ..Default::default()
+            },
+            true,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(error.to_string(), "Timesheet entry 8 does not exist");
+
+        let entry = get_entry(&pool, TEST_PROJECT, 7).await.unwrap();
+        assert_eq!((entry.task_id, entry.date, entry.days), (1, date(20), 3));
+    }
+
+    #[tokio::test]
+    async fn deletes_the_entry() {
+        let pool = pool_with_entry().await;
+        delete(&pool, TEST_PROJECT, &Config::default(), 7, true)
+            .await
+            .unwrap();
+
+        let error = get_entry(&pool, TEST_PROJECT, 7).await.err().unwrap();
+        assert_eq!(error.to_string(), "Timesheet entry 7 does not exist");
+        let error = delete(&pool, TEST_PROJECT, &Config::default(), 7, true)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "Timesheet entry 7 does not exist");
+    }
+
+    #[tokio::test]
+    async fn totals_days_per_person_and_week() {
+        let pool = pool_with_entry().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO timesheet (project_id, task_id, date, duration, person)
+            VALUES (1, 1, '2026-10-23', 1, 'dana'),
+                   (1, 2, '2026-10-22', 2, 'bob'),
+                   (1, 2, '2026-10-26', 4, 'bob'),
+                   (1, 2, '2026-10-27', 1, NULL),
+                   (1, 2, '2026-11-02', 5, 'bob');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let totals = weekly_totals(&pool, TEST_PROJECT, None, Some(date(30)))
+            .await
+            .unwrap();
+        let week = |monday, days: &[(&str, i64)]| {
+            (
+                date(monday),
+                days.iter()
+                    .map(|(person, days)| (person.to_string(), *days))
+                    .collect::<BTreeMap<String, i64>>(),
+            )
+        };
+        assert_eq!(
+            totals,
+            BTreeMap::from([
+                week(19, &[("bob", 2), ("dana", 4)]),
+                week(26, &[("bob", 4), ("unknown", 1)]),
+            ])
+        );
+
+        let totals = weekly_totals(&pool, TEST_PROJECT, Some(date(21)), Some(date(23)))
+            .await
+            .unwrap();
+        assert_eq!(
+            totals,
+            BTreeMap::from([week(19, &[("bob", 2), ("dana", 1)])])
+        );
+    }
+
+    #[tokio::test]
+    async fn logged_work_follows_the_team_member() {
+        let pool = pool_with_entry().await;
+        sqlx::query!(r#"UPDATE team SET name = 'Dana Scully' WHERE id = 1"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let totals = weekly_totals(&pool, TEST_PROJECT, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            totals,
+            BTreeMap::from([(date(19), BTreeMap::from([("dana scully".to_string(), 3)]))])
+        );
+
+        // Work handed to someone outside the team and back is linked again
+        for (person, member_id) in [("bob", None), ("Dana Scully", Some(1))] {
+            edit(
+                &pool,
+                TEST_PROJECT,
+                &Config::default(),
+                7,
+                EntryChanges {
+                    person: Some(person.to_string()),
+                    ..Default::default()
+                },
+                true,
+            )
+            .await
+            .unwrap();
+            let entry = get_entry(&pool, TEST_PROJECT, 7).await.unwrap();
+            assert_eq!(
+                entry.person.as_deref(),
+                Some(person.to_lowercase().as_str())
+            );
+            assert_eq!(entry.member_id, member_id);
+        }
+    }
+
+    #[tokio::test]
+    async fn moved_entries_must_be_on_a_working_day() {
+        let pool = pool_with_entry().await;
+        sqlx::query!(r#"INSERT INTO calendar_days_off (project_id, member_id, date) VALUES (1, 1, '2026-10-22')"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for day in [25, 22] {
+            let error = edit(
+                &pool,
+                TEST_PROJECT,
+                &Config::default(),
+                7,
+                EntryChanges {
+                    date: Some(date(day)),
+                    ..Default::default()
+                },
+                true,
+            )
+            .await
+            .err()
+            .unwrap();
+            assert!(
+                error.to_string().ends_with("is not a working day of dana"),
+                "{}",
+                error
+            );
+        }
+        edit(
+            &pool,
+            TEST_PROJECT,
+            &Config::default(),
+            7,
+            EntryChanges {
+                date: Some(date(21)),
+                ..Default::default()
+            },
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            get_entry(&pool, TEST_PROJECT, 7).await.unwrap().date,
+            date(21)
+        );
+    }
+
+    #[tokio::test]
+    async fn entries_on_days_off_keep_their_days_editable() {
+        let pool = pool_with_entry().await;
+        // Dana takes the day of the entry off after logging it
+        sqlx::query!(r#"INSERT INTO calendar_days_off (project_id, member_id, date) VALUES (1, 1, '2026-10-20')"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        edit(
+            &pool,
+            TEST_PROJECT,
+            &Config::default(),
+            7,
+            EntryChanges {
+                days: Some(2),
+                ..Default::default()
+            },
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_entry(&pool, TEST_PROJECT, 7).await.unwrap().days, 2);
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use anyhow::bail;
+use colored::Colorize;
+use prettytable::{row, Table};
+use sqlx::SqlitePool;
+
+use crate::project::{dependency, load_plan, MsProjectTask};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Severity {
+    // The plan cannot be imported
+    Error,
+    // Probably a mistake in the plan, but it can be scheduled
+    Warning,
+}
+
+#[derive(Debug)]
+pub(crate) struct Problem {
+    pub(crate) severity: Severity,
+    // Line in the imported file, unknown for tasks in the database
+    pub(crate) line: Option<u64>,
+    pub(crate) task_id: Option<i64>,
+    pub(crate) message: String,
+}
+
+impl Problem {
+    pub(crate) fn error(line: Option<u64>, task_id: Option<i64>, message: String) -> Self {
+        Problem {
+            severity: Severity::Error,
+            line,
+            task_id,
+            message,
+        }
+    }
+
+    fn warning(line: Option<u64>, task_id: Option<i64>, message: String) -> Self {
+        Problem {
+            severity: Severity::Warning,
+            line,
+            task_id,
+            message,
+        }
+    }
+}
+
+// Check a MS Project file without importing it, or the plan in the database without a file
+pub(crate) async fn validate(
+    pool: &SqlitePool,
+    project_id: i64,
+    file: &Option<String>,
+) -> anyhow::Result<()> {
+    let (tasks, mut problems) = match file {
+        Some(file) => load_plan(file)
+            .map_err(|e| anyhow::anyhow!("Failed to load tasks from {}: {}", file, e))?,
+        None => (load_stored_plan(pool, project_id).await?, vec![]),
+    };
+    problems.extend(check_tasks(&tasks));
+
+    let source = file.as_deref().unwrap_or("the database");
+    if problems.is_empty() {
+        println!("✅ No problems in {} tasks from {}", tasks.len(), source);
+        return Ok(());
+    }
+    print_problems(&problems);
+    let errors = count_errors(&problems);
+    if errors > 0 {
+        bail!("{} errors in {}", errors, source);
+    }
+    Ok(())
+}
+
+// Report the problems found while importing, refusing plans with errors
+pub(super) fn check_import(
+    file: &str,
+    tasks: &[MsProjectTask],
+    mut problems: Vec<Problem>,
+) -> anyhow::Result<()> {
+    problems.extend(check_tasks(tasks));
+    if problems.is_empty() {
+        return Ok(());
+    }
+    print_problems(&problems);
+    let errors = count_errors(&problems);
+    if errors > 0 {
+        bail!(
+            "{} errors in {}, fix them and import again (nothing was imported)",
+            errors,
+            file
+        );
+    }
+    Ok(())
+}
+
+// Structural checks of the task network: duplicate IDs, unreadable links, links to tasks that
+// do not exist, dependency cycles and tasks nothing depends on
+fn check_tasks(tasks: &[MsProjectTask]) -> Vec<Problem> {
+    let mut problems = vec![];
+
+    let mut lines: HashMap<i64, Option<u64>> = HashMap::new();
+    for task in tasks {
+        let id = task.id as i64;
+        match lines.get(&id) {
+            Some(line) => problems.push(Problem::error(
+                task.line,
+                Some(id),
+                match line {
+                    Some(line) => format!("Duplicate ID #{}, already used on line {}", id, line),
+                    None => format!("Duplicate ID #{}", id),
+                },
+            )),
+            None => {
+                lines.insert(id, task.line);
+            }
+        }
+    }
+
+    let mut predecessors: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+    let mut has_successors: HashSet<i64> = HashSet::new();
+    for task in tasks {
+        let id = task.id as i64;
+        let links = predecessors.entry(id).or_default();
+        for link in task
+            .predecessors
+            .split(',')
+            .map(str::trim)
+            .filter(|link| !link.is_empty())
+        {
+            match dependency::parse_link(link).map(|link| link.predecessor) {
+                Ok(predecessor) if !lines.contains_key(&predecessor) => {
+                    problems.push(Problem::error(
+                        task.line,
+                        Some(id),
+                        format!("Predecessor #{} does not exist", predecessor),
+                    ))
+                }
+                Ok(predecessor) if links.contains(&predecessor) => problems.push(Problem::error(
+                    task.line,
+                    Some(id),
+                    format!("Predecessor #{} is linked more than once", predecessor),
+                )),
+                Ok(predecessor) => {
+                    links.push(predecessor);
+                    has_successors.insert(predecessor);
+                }
+                Err(reason) => problems.push(Problem::error(
+                    task.line,
+                    Some(id),
+                    format!("Cannot read predecessor \"{}\", {}", link, reason),
+                )),
+            }
+        }
+    }
+
+    for cycle in find_cycles(&predecessors) {
+        problems.push(Problem::error(
+            lines.get(&cycle[0]).copied().flatten(),
+            Some(cycle[0]),
+            format!(
+                "Dependency cycle {}",
+                cycle
+                    .iter()
+                    .chain(cycle.first())
+                    .map(|id| format!("#{}", id))
+                    .collect::<Vec<String>>()
+                    .join(" → ")
+            ),
+        ));
+    }
+
+    // Only the tasks finishing the project may end a chain of dependencies
+    let project_finish = tasks.iter().map(|task| task.finish_date).max();
+    for task in tasks {
+        let id = task.id as i64;
+        if !has_successors.contains(&id) && Some(task.finish_date) != project_finish {
+            problems.push(Problem::warning(
+                task.line,
+                Some(id),
+                format!("#{} {} has no successors", id, task.name),
+            ));
+        }
+    }
+
+    problems
+}
+
+// Cycles in the dependency graph, each as the tasks on it starting with the lowest ID
+fn find_cycles(predecessors: &BTreeMap<i64, Vec<i64>>) -> Vec<Vec<i64>> {
+    let mut cycles: Vec<Vec<i64>> = vec![];
+    let mut done: HashSet<i64> = HashSet::new();
+
+    for start in predecessors.keys() {
+        if done.contains(start) {
+            continue;
+        }
+        // Depth first with an explicit stack of (task, index of the next predecessor to visit)
+        let mut path: Vec<(i64, usize)> = vec![(*start, 0)];
+        while let Some((id, next)) = path.last().copied() {
+            let links = predecessors.get(&id).map(Vec::as_slice).unwrap_or_default();
+            match links.get(next) {
+                Some(predecessor) => {
+                    path.last_mut().expect("Empty path").1 += 1;
+                    if let Some(position) = path.iter().position(|(id, _)| id == predecessor) {
+                        let mut cycle: Vec<i64> =
+                            path[position..].iter().map(|(id, _)| *id).collect();
+                        cycle.reverse();
+                        let lowest = cycle
+                            .iter()
+                            .enumerate()
+                            .min_by_key(|(_, id)| **id)
+                            .map(|(i, _)| i)
+                            .unwrap_or(0);
+                        cycle.rotate_left(lowest);
+                        if !cycles.contains(&cycle) {
+                            cycles.push(cycle);
+                        }
+                    } else if !done.contains(predecessor) {
+                        path.push((*predecessor, 0));
+                    }
+                }
+                None => {
+                    done.insert(id);
+                    path.pop();
+                }
+            }
+        }
+    }
+    cycles
+}
+
+fn count_errors(problems: &[Problem]) -> usize {
+    problems
+        .iter()
+        .filter(|problem| problem.severity == Severity::Error)
+        .count()
+}
+
+fn print_problems(problems: &[Problem]) {
+    let mut problems: Vec<&Problem> = problems.iter().collect();
+    problems.sort_by_key(|problem| (problem.line, problem.task_id));
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "Line".bold(),
+        "#".bold(),
+        "Severity".bold(),
+        "Problem".bold()
+    ]);
+    for problem in problems {
+        table.add_row(row![
+            problem
+                .line
+                .map(|line| line.to_string())
+                .unwrap_or("--".to_string()),
+            problem
+                .task_id
+                .map(|id| id.to_string())
+                .unwrap_or("--".to_string()),
+            match problem.severity {
+                Severity::Error => "error".red(),
+                Severity::Warning => "warning".yellow(),
+            },
+            problem.message
+        ]);
+    }
+    table.printstd();
+}
+
+// The plan as stored, in the shape it was imported in
+async fn load_stored_plan(
+    pool: &SqlitePool,
+    project_id: i64,
+) -> anyhow::Result<Vec<MsProjectTask>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id               as "id!: i32",
+               name,
+               duration         as "duration: i32",
+               start_date,
+               finish_date,
+               total_slack      as "total_slack: i32",
+               resource_names,
+               pdex_criticality as "pdex_criticality: i32"
+        FROM tasks
+        WHERE project_id = ?1
+        ORDER BY id
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let links = dependency::load(pool, project_id).await?;
+    let mut tasks = vec![];
+    for row in rows {
+        tasks.push(MsProjectTask {
+            id: row.id,
+            name: row.name,
+            duration: row.duration,
+            predecessors: links
+                .get(&(row.id as i64))
+                .map(|links| dependency::format_links(links))
+                .unwrap_or_default(),
+            start_date: row.start_date.parse()?,
+            finish_date: row.finish_date.parse()?,
+            total_slack: row.total_slack,
+            resource_names: row.resource_names.unwrap_or_default(),
+            pdex_criticality: row.pdex_criticality,
+            line: None,
+        });
+    }
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn task(id: i32, predecessors: &str, finish_day: u32) -> MsProjectTask {
+        MsProjectTask {
+            id,
+            name: format!("Task {}", id),
+            duration: 1,
+            predecessors: predecessors.to_string(),
+            start_date: NaiveDate::from_ymd_opt(2026, 10, finish_day).unwrap(),
+            finish_date: NaiveDate::from_ymd_opt(2026, 10, finish_day).unwrap(),
+            total_slack: 0,
+            resource_names: String::new(),
+            pdex_criticality: None,
+            line: Some(id as u64 + 1),
+        }
+    }
+
+    fn messages(problems: &[Problem], severity: Severity) -> Vec<&str> {
+        problems
+            .iter()
+            .filter(|problem| problem.severity == severity)
+            .map(|problem| problem.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn finds_no_cycles_in_a_chain() {
+        let predecessors = BTreeMap::from([(1, vec![]), (2, vec![1]), (3, vec![1, 2])]);
+        assert!(find_cycles(&predecessors).is_empty());
+    }
+
+    #[test]
+    fn finds_each_cycle_once_starting_with_the_lowest_id() {
+        let predecessors = BTreeMap::from([
+            (1, vec![3]),
+            (2, vec![1]),
+            (3, vec![2]),
+            (4, vec![4]),
+            (5, vec![1]),
+        ]);
+        assert_eq!(find_cycles(&predecessors), vec![vec![1, 2, 3], vec![4]]);
+    }
+
+    #[test]
+    fn accepts_a_sound_plan() {
+        let tasks = vec![
+            task(1, "", 19),
+            task(2, "1", 20),
+            task(3, "1SS, 2FS+1 day", 22),
+        ];
+        assert!(check_tasks(&tasks).is_empty());
+    }
+
+    #[test]
+    fn reports_broken_links_as_errors() {
+        let tasks = vec![
+            task(1, "", 19),
+            task(1, "", 19),
+            task(2, "1,1SS", 20),
+            task(3, "9", 20),
+            task(4, "2XY", 20),
+            task(5, "6", 22),
+            task(6, "5", 22),
+        ];
+        assert_eq!(
+            messages(&check_tasks(&tasks), Severity::Error),
+            vec![
+                "Duplicate ID #1, already used on line 2",
+                "Predecessor #1 is linked more than once",
+                "Predecessor #9 does not exist",
+                "Cannot read predecessor \"2XY\", XY is not a link type (FS, SS, FF or SF)",
+                "Dependency cycle #5 → #6 → #5",
+            ]
+        );
+    }
+
+    #[test]
+    fn warns_about_loose_ends_before_the_project_finish() {
+        let tasks = vec![task(1, "", 19), task(2, "", 20), task(3, "1", 22)];
+        assert_eq!(
+            messages(&check_tasks(&tasks), Severity::Warning),
+            vec!["#2 Task 2 has no successors"]
+        );
+    }
+}